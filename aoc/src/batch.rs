@@ -0,0 +1,212 @@
+//! Run a day against every input of a directory and check the answers we know.
+
+use crate::days::Day;
+use crate::solution::Report;
+use serde::Deserialize;
+use std::fs;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+
+/// Suffix of the file holding the expected answers of an input, eg: `test2.expected.json`.
+pub const SIDECAR_SUFFIX: &str = ".expected.json";
+
+/// Answers an input should give. A missing part is not checked.
+#[derive(Debug, Default, Deserialize)]
+pub struct Expected {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    Pass(String),
+    Fail { answer: String, expected: String },
+    Unchecked(String),
+    Error(String),
+}
+
+impl Outcome {
+    fn check(answer: &Result<String, String>, expected: &Option<String>) -> Outcome {
+        match (answer, expected) {
+            (Err(e), _) => Outcome::Error(e.clone()),
+            (Ok(a), Some(e)) if a.trim_end() == e.trim_end() => Outcome::Pass(a.clone()),
+            (Ok(a), Some(e)) => Outcome::Fail {
+                answer: a.clone(),
+                expected: e.clone(),
+            },
+            (Ok(a), None) => Outcome::Unchecked(a.clone()),
+        }
+    }
+
+    pub fn failed(&self) -> bool {
+        matches!(self, Outcome::Fail { .. } | Outcome::Error(_))
+    }
+
+    fn cell(&self) -> String {
+        let short = |a: &str| {
+            if a.contains('\n') {
+                "(screen)".to_string()
+            } else {
+                a.to_string()
+            }
+        };
+        match self {
+            Outcome::Pass(a) => format!("ok   {}", short(a)),
+            Outcome::Fail { answer, expected } => {
+                format!("FAIL {} != {}", short(answer), short(expected))
+            }
+            Outcome::Unchecked(a) => format!("?    {}", short(a)),
+            Outcome::Error(e) => format!("ERR  {e}"),
+        }
+    }
+}
+
+/// Result of running a day on one input file.
+#[derive(Debug)]
+pub struct Entry {
+    pub input: PathBuf,
+    pub part1: Outcome,
+    pub part2: Outcome,
+}
+
+/// Answers stored next to `input`, none if there is no sidecar.
+pub fn read_expected(input: &Path) -> Result<Expected, String> {
+    let mut sidecar = input.as_os_str().to_owned();
+    sidecar.push(SIDECAR_SUFFIX);
+    let sidecar = PathBuf::from(sidecar);
+    match fs::read_to_string(&sidecar) {
+        Ok(json) => serde_json::from_str(&json)
+            .map_err(|e| format!("Invalid answers in {}: {e}", sidecar.display())),
+        Err(_) => Ok(Expected::default()),
+    }
+}
+
+/// Check both parts of a report against the expected answers.
+pub fn outcomes(report: &Report, expected: &Expected) -> [Outcome; 2] {
+    let answer = |step: &str| {
+        report
+            .steps
+            .iter()
+            .find(|m| m.step == step)
+            .map(|m| m.answer.clone())
+            // parsing failed, the parts did not run
+            .unwrap_or_else(|| Err(report.steps[0].answer.clone().unwrap_err()))
+    };
+    [
+        Outcome::check(&answer("part1"), &expected.part1),
+        Outcome::check(&answer("part2"), &expected.part2),
+    ]
+}
+
+/// Solve one input and check its answers. An input that cannot be read or a solver that panics
+/// fails both parts.
+fn check(day: &Day, input: &Path) -> Result<[Outcome; 2], String> {
+    let expected = read_expected(input)?;
+    let content = fs::read_to_string(input)
+        .map_err(|e| format!("Unable to read {}: {e}", input.display()))?;
+    let report = catch_unwind(AssertUnwindSafe(|| (day.run)(day.year, day.day, &content)))
+        .map_err(|_| format!("The solver of day {} panicked", day.day))?;
+    Ok(outcomes(&report, &expected))
+}
+
+/// Run the day on every file of `dir`, sidecars excepted, in name order.
+pub fn batch(day: &Day, dir: &Path) -> Result<Vec<Entry>, String> {
+    let mut inputs = fs::read_dir(dir)
+        .map_err(|e| format!("Unable to read {}: {e}", dir.display()))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| p.is_file())
+        .filter(|p| !p.to_string_lossy().ends_with(SIDECAR_SUFFIX))
+        .collect::<Vec<PathBuf>>();
+    inputs.sort();
+
+    Ok(inputs
+        .into_iter()
+        .map(|input| {
+            let [part1, part2] = check(day, &input)
+                .unwrap_or_else(|e| [Outcome::Error(e.clone()), Outcome::Error(e)]);
+            Entry {
+                part1,
+                part2,
+                input,
+            }
+        })
+        .collect())
+}
+
+pub fn print_table(entries: &[Entry]) {
+    let width = entries
+        .iter()
+        .map(|e| e.input.display().to_string().len())
+        .max()
+        .unwrap_or(0);
+    println!("{:<width$}  {:<30} part2", "input", "part1");
+    for e in entries {
+        println!(
+            "{:<width$}  {:<30} {}",
+            e.input.display(),
+            e.part1.cell(),
+            e.part2.cell()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+
+    #[test]
+    fn check_answers() {
+        let expected = Some("13".to_string());
+        assert_eq!(
+            Outcome::check(&Ok("13".to_string()), &expected),
+            Outcome::Pass("13".to_string())
+        );
+        assert!(Outcome::check(&Ok("12".to_string()), &expected).failed());
+        assert!(Outcome::check(&Err("bad".to_string()), &None).failed());
+        assert!(!Outcome::check(&Ok("12".to_string()), &None).failed());
+    }
+
+    #[test]
+    fn batch_day9_examples() {
+        let dir = std::env::temp_dir().join(format!("aoc-batch-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("small"),
+            "R 4\nU 4\nL 3\nD 1\nR 4\nD 1\nL 5\nR 2\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("small.expected.json"),
+            r#"{"part1": "13", "part2": "2"}"#,
+        )
+        .unwrap();
+        fs::write(dir.join("broken"), "X 4\n").unwrap();
+        fs::write(dir.join("latin1"), b"R 4\xe9\n").unwrap();
+        fs::write(dir.join("unchecked"), "R 4\n").unwrap();
+        fs::write(dir.join("unchecked.expected.json"), "{").unwrap();
+
+        let day9 = days::find(2022, 9).unwrap();
+        let entries = batch(day9, &dir).unwrap();
+        let panicking = Day {
+            run: |_, _, _| panic!("boom"),
+            ..*day9
+        };
+        let panics = batch(&panicking, &dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(entries.len(), 4);
+        assert!(entries[0].input.ends_with("broken"));
+        assert!(entries[0].part1.failed());
+        // Unreadable inputs and sidecars fail their own row only.
+        assert!(matches!(&entries[1].part1, Outcome::Error(e) if e.contains("latin1")));
+        assert_eq!(entries[2].part1, Outcome::Pass("13".to_string()));
+        assert!(entries[2].part2.failed());
+        assert!(matches!(&entries[3].part2, Outcome::Error(e) if e.contains("Invalid answers")));
+
+        // The readable inputs reach the solver, which panics on each of them.
+        let panicked = Outcome::Error("The solver of day 9 panicked".to_string());
+        assert_eq!(panics.len(), 4);
+        assert_eq!((&panics[0].part1, &panics[2].part2), (&panicked, &panicked));
+    }
+}
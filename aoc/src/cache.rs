@@ -0,0 +1,119 @@
+//! Opt-in cache of parsed models, so repeated runs on the same input can skip parsing.
+//!
+//! Entries are keyed by the input, the model type, the runner version and the runner binary
+//! itself: rebuilding the runner or changing the input picks a new entry. Writing an entry
+//! removes those of the same day left by other builds.
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+/// Folder of the cache entries.
+pub const CACHE_DIR: &str = ".aoc-cache";
+
+/// Changes whenever the runner is rebuilt.
+fn binary_fingerprint() -> Option<(u64, std::time::SystemTime)> {
+    let meta = fs::metadata(std::env::current_exe().ok()?).ok()?;
+    Some((meta.len(), meta.modified().ok()?))
+}
+
+/// Identifies the builds that can read each other's models.
+fn build<M>() -> u64 {
+    let mut hasher = DefaultHasher::new();
+    std::any::type_name::<M>().hash(&mut hasher);
+    env!("CARGO_PKG_VERSION").hash(&mut hasher);
+    binary_fingerprint().hash(&mut hasher);
+    hasher.finish()
+}
+
+fn day_prefix(year: u32, day: u32) -> String {
+    format!("{year}-day{day}-")
+}
+
+/// File holding the model of the puzzle parsed from `input`.
+pub fn entry<M>(dir: &Path, year: u32, day: u32, input: &str) -> PathBuf {
+    let mut hasher = DefaultHasher::new();
+    input.hash(&mut hasher);
+    dir.join(format!(
+        "{}{:016x}-{:016x}.bin",
+        day_prefix(year, day),
+        build::<M>(),
+        hasher.finish()
+    ))
+}
+
+/// Remove the entries of the day written by other builds, which can no longer be read.
+fn prune<M>(dir: &Path, year: u32, day: u32) {
+    let prefix = day_prefix(year, day);
+    let current = format!("{prefix}{:016x}-", build::<M>());
+    for e in fs::read_dir(dir).into_iter().flatten().flatten() {
+        let name = e.file_name().to_string_lossy().into_owned();
+        if name.starts_with(&prefix) && !name.starts_with(&current) {
+            let _ = fs::remove_file(e.path());
+        }
+    }
+}
+
+/// The cached model, if there is a valid one.
+pub fn load<M: DeserializeOwned>(dir: &Path, year: u32, day: u32, input: &str) -> Option<M> {
+    let bytes = fs::read(entry::<M>(dir, year, day, input)).ok()?;
+    bincode::deserialize(&bytes).ok()
+}
+
+/// Save the model. Failing to write the cache only costs a parse next time.
+pub fn store<M: Serialize>(dir: &Path, year: u32, day: u32, input: &str, model: &M) {
+    if let Ok(bytes) = bincode::serialize(model) {
+        prune::<M>(dir, year, day);
+        let _ = fs::create_dir_all(dir)
+            .and_then(|_| fs::write(entry::<M>(dir, year, day, input), bytes));
+    }
+}
+
+/// Remove every entry, returning how many there were.
+pub fn clear(dir: &Path) -> Result<usize, String> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries.filter_map(|e| e.ok()).collect::<Vec<_>>(),
+        Err(_) => return Ok(0),
+    };
+    for e in &entries {
+        fs::remove_file(e.path()).map_err(|err| format!("{}: {err}", e.path().display()))?;
+    }
+    Ok(entries.len())
+}
+
+#[test]
+fn reuse_models_of_the_same_input() {
+    let dir = std::env::temp_dir().join(format!("aoc-cache-{}", std::process::id()));
+    let model = vec![(2, 4), (6, 8)];
+    assert_eq!(load::<Vec<(i32, i32)>>(&dir, 2022, 4, "2-4,6-8"), None);
+
+    store(&dir, 2022, 4, "2-4,6-8", &model);
+    assert_eq!(load(&dir, 2022, 4, "2-4,6-8"), Some(model));
+    assert_eq!(load::<Vec<(i32, i32)>>(&dir, 2022, 4, "2-4,6-9"), None);
+    assert_eq!(load::<Vec<(i64, i64)>>(&dir, 2022, 4, "2-4,6-8"), None);
+    assert_eq!(load::<Vec<(i32, i32)>>(&dir, 2023, 4, "2-4,6-8"), None);
+
+    assert_eq!(clear(&dir), Ok(1));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn prune_entries_of_other_builds() {
+    let dir = std::env::temp_dir().join(format!("aoc-cache-prune-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let stale = dir.join(format!("2022-day4-{:016x}-0000000000000000.bin", 0));
+    let other_day = dir.join(format!("2022-day40-{:016x}-0000000000000000.bin", 0));
+    fs::write(&stale, b"old build").unwrap();
+    fs::write(&other_day, b"old build").unwrap();
+
+    store(&dir, 2022, 4, "2-4,6-8", &vec![(2, 4), (6, 8)]);
+    store(&dir, 2022, 4, "2-4,6-9", &vec![(2, 4), (6, 9)]);
+    assert!(!stale.exists());
+    assert!(other_day.exists());
+    // Both inputs of the current build are kept.
+    assert_eq!(clear(&dir), Ok(3));
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,377 @@
+//! Glue between the runner and the crate of every day.
+
+use crate::solution::{run, run_cached, run_part, run_uncached, Report, Solution};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+/// The year of the first puzzles solved in this repository.
+pub const FIRST_YEAR: u32 = 2022;
+
+/// A day registered in the runner. Puzzles are identified by their year and day.
+pub struct Day {
+    pub year: u32,
+    pub day: u32,
+    /// Folder holding the puzzle input. Days of 2022 live at the root, eg: `day5`, later years
+    /// go in a folder per year, eg: `2023/day5`.
+    pub folder: &'static str,
+    pub run: fn(u32, u32, &str) -> Report,
+    /// `run`, solving only the given part.
+    pub run_part: fn(u32, u32, &str, u32) -> Report,
+    /// `run`, reusing the models cached in the given folder.
+    pub run_cached: fn(u32, u32, &str, &Path) -> Report,
+    /// Crates holding the solution, and their tests.
+    pub packages: &'static [&'static str],
+    /// Why the solution is not complete yet, if it is not.
+    pub partial: Option<&'static str>,
+}
+
+impl Day {
+    /// Days keep their puzzle input either in `input` or `input.txt`.
+    pub fn default_input(&self) -> PathBuf {
+        let input = PathBuf::from(self.folder).join("input");
+        if input.exists() {
+            input
+        } else {
+            input.with_file_name("input.txt")
+        }
+    }
+}
+
+pub const DAYS: &[Day] = &[
+    Day {
+        year: 2022,
+        day: 1,
+        folder: "day1",
+        run: run::<Day1>,
+        run_part: run_part::<Day1>,
+        run_cached: run_cached::<Day1>,
+        packages: &["day1a"],
+        partial: None,
+    },
+    Day {
+        year: 2022,
+        day: 2,
+        folder: "day2",
+        run: run::<Day2>,
+        run_part: run_part::<Day2>,
+        run_cached: run_cached::<Day2>,
+        packages: &["day2"],
+        partial: None,
+    },
+    Day {
+        year: 2022,
+        day: 3,
+        folder: "day3",
+        run: run::<Day3>,
+        run_part: run_part::<Day3>,
+        run_cached: run_uncached::<Day3>,
+        packages: &["day3", "day3b"],
+        partial: None,
+    },
+    Day {
+        year: 2022,
+        day: 4,
+        folder: "day4a",
+        run: run::<Day4>,
+        run_part: run_part::<Day4>,
+        run_cached: run_cached::<Day4>,
+        packages: &["day4a", "day4b"],
+        partial: None,
+    },
+    Day {
+        year: 2022,
+        day: 5,
+        folder: "day5",
+        run: run::<Day5>,
+        run_part: run_part::<Day5>,
+        run_cached: run_cached::<Day5>,
+        packages: &["day5"],
+        partial: None,
+    },
+    Day {
+        year: 2022,
+        day: 6,
+        folder: "day6",
+        run: run::<Day6>,
+        run_part: run_part::<Day6>,
+        run_cached: run_uncached::<Day6>,
+        packages: &["day6"],
+        partial: None,
+    },
+    Day {
+        year: 2022,
+        day: 7,
+        folder: "day7",
+        run: run::<Day7>,
+        run_part: run_part::<Day7>,
+        run_cached: run_cached::<Day7>,
+        packages: &["day7"],
+        partial: None,
+    },
+    Day {
+        year: 2022,
+        day: 8,
+        folder: "day8",
+        run: run::<Day8>,
+        run_part: run_part::<Day8>,
+        run_cached: run_cached::<Day8>,
+        packages: &["day8"],
+        partial: None,
+    },
+    Day {
+        year: 2022,
+        day: 9,
+        folder: "day9",
+        run: run::<Day9>,
+        run_part: run_part::<Day9>,
+        run_cached: run_cached::<Day9>,
+        packages: &["day9"],
+        partial: None,
+    },
+    Day {
+        year: 2022,
+        day: 10,
+        folder: "day10",
+        run: run::<Day10>,
+        run_part: run_part::<Day10>,
+        run_cached: run_cached::<Day10>,
+        packages: &["day10"],
+        partial: None,
+    },
+    Day {
+        year: 2022,
+        day: 11,
+        folder: "day11",
+        run: run::<Day11>,
+        run_part: run_part::<Day11>,
+        run_cached: run_cached::<Day11>,
+        packages: &["day11"],
+        partial: Some("the input is hardcoded"),
+    },
+];
+
+pub fn find(year: u32, day: u32) -> Result<&'static Day, String> {
+    DAYS.iter()
+        .find(|d| d.year == year && d.day == day)
+        .ok_or(format!("Day {day} of {year} is not solved yet."))
+}
+
+/// The days registered for a year.
+pub fn of_year(year: u32) -> impl Iterator<Item = &'static Day> {
+    DAYS.iter().filter(move |d| d.year == year)
+}
+
+/// The most recent year with a registered day, used when none is given.
+pub fn latest_year() -> u32 {
+    DAYS.iter().map(|d| d.year).max().unwrap_or(FIRST_YEAR)
+}
+
+pub struct Day1;
+impl Solution for Day1 {
+    type Model = Vec<day1a::ElfDiet>;
+
+    fn parse(input: &str) -> Result<Self::Model, String> {
+        day1a::parse_diets(input).map_err(|e| e.to_string())
+    }
+    fn part1(model: &Self::Model) -> Result<String, String> {
+        let best = model.iter().map(|d| d.total_calories).max();
+        best.map(|c| c.to_string()).ok_or("No elves".to_string())
+    }
+    fn part2(model: &Self::Model) -> Result<String, String> {
+        let top = day1a::top_elves(model.iter().cloned(), 3);
+        Ok(day1a::total_calories(&top)?.to_string())
+    }
+}
+
+pub struct Day2;
+impl Solution for Day2 {
+    type Model = Vec<day2::GuideRound>;
+
+    fn parse(input: &str) -> Result<Self::Model, String> {
+        day2::parse_guide(input).map_err(|e| e.to_string())
+    }
+    fn part1(model: &Self::Model) -> Result<String, String> {
+        let [score] = day2::total_scores(model, [&day2::AsShape]);
+        Ok(score.to_string())
+    }
+    fn part2(model: &Self::Model) -> Result<String, String> {
+        let [score] = day2::total_scores(model, [&day2::AsResult]);
+        Ok(score.to_string())
+    }
+}
+
+pub struct Day3;
+impl Solution for Day3 {
+    type Model = String;
+
+    fn parse(input: &str) -> Result<Self::Model, String> {
+        Ok(input.to_string())
+    }
+    fn part1(model: &Self::Model) -> Result<String, String> {
+        day3::total_priority(model)
+            .map(|total| total.to_string())
+            .map_err(|e| e.to_string())
+    }
+    fn part2(model: &Self::Model) -> Result<String, String> {
+        Ok(day3b::badges_priority(model)?.to_string())
+    }
+}
+
+pub struct Day4;
+impl Solution for Day4 {
+    type Model = Vec<((i32, i32), (i32, i32))>;
+
+    fn parse(input: &str) -> Result<Self::Model, String> {
+        parser::parse_lines(day4a::assignment, input).map_err(|e| e.to_string())
+    }
+    fn part1(model: &Self::Model) -> Result<String, String> {
+        Ok(model
+            .iter()
+            .filter(|p| day4a::fully_contains(p))
+            .count()
+            .to_string())
+    }
+    fn part2(model: &Self::Model) -> Result<String, String> {
+        Ok(model
+            .iter()
+            .filter(|p| day4b::overlaps(p))
+            .count()
+            .to_string())
+    }
+}
+
+pub struct Day5;
+impl Solution for Day5 {
+    type Model = (day5::Dock, Vec<day5::MoveOrder>);
+
+    fn parse(input: &str) -> Result<Self::Model, String> {
+        day5::parse(input).map_err(|e| e.to_string())
+    }
+    fn part1(model: &Self::Model) -> Result<String, String> {
+        let mut dock = model.0.clone();
+        for m in &model.1 {
+            dock.run9000(m).ok_or("Invalid move")?;
+        }
+        Ok(dock.tops())
+    }
+    fn part2(model: &Self::Model) -> Result<String, String> {
+        let mut dock = model.0.clone();
+        for m in &model.1 {
+            dock.run9001(m).ok_or("Invalid move")?;
+        }
+        Ok(dock.tops())
+    }
+}
+
+pub struct Day6;
+impl Solution for Day6 {
+    type Model = String;
+
+    fn parse(input: &str) -> Result<Self::Model, String> {
+        Ok(input.to_string())
+    }
+    fn part1(model: &Self::Model) -> Result<String, String> {
+        Ok(day6::find_marker(model, 4)?.to_string())
+    }
+    fn part2(model: &Self::Model) -> Result<String, String> {
+        Ok(day6::process_buf(model)?.to_string())
+    }
+}
+
+pub struct Day7;
+impl Solution for Day7 {
+    type Model = Rc<day7::filetree::FileTree>;
+
+    fn parse(input: &str) -> Result<Self::Model, String> {
+        let parsed = day7::parse_transcript(input).map_err(|e| e.to_string())?;
+        Ok(day7::filetree::build_tree(&mut parsed.iter())?)
+    }
+    fn part1(model: &Self::Model) -> Result<String, String> {
+        Ok(model.less_than_10000().to_string())
+    }
+    fn part2(model: &Self::Model) -> Result<String, String> {
+        let size = model
+            .smallest_deletable()
+            .ok_or("No directory is big enough")?;
+        Ok(size.to_string())
+    }
+}
+
+pub struct Day8;
+impl Solution for Day8 {
+    type Model = day8::Garden;
+
+    fn parse(input: &str) -> Result<Self::Model, String> {
+        day8::Garden::try_from(input).map_err(|e| e.to_string())
+    }
+    fn part1(model: &Self::Model) -> Result<String, String> {
+        let visible = model.visible_trees().ok_or("Empty garden")?;
+        Ok(visible.to_string())
+    }
+    fn part2(model: &Self::Model) -> Result<String, String> {
+        let best = model.best_scenic_score().ok_or("Empty garden")?;
+        Ok(best.to_string())
+    }
+}
+
+fn tail_visits(knots: usize, moves: &[day9::point::Move]) -> String {
+    let mut rope = day9::rope::Rope::new(knots, 0, 0);
+    for m in moves {
+        rope.move_head(*m);
+    }
+    rope.tail_visits().to_string()
+}
+
+pub struct Day9;
+impl Solution for Day9 {
+    type Model = Vec<day9::point::Move>;
+
+    fn parse(input: &str) -> Result<Self::Model, String> {
+        day9::parse_moves(input).map_err(|e| e.to_string())
+    }
+    fn part1(model: &Self::Model) -> Result<String, String> {
+        Ok(tail_visits(2, model))
+    }
+    fn part2(model: &Self::Model) -> Result<String, String> {
+        Ok(tail_visits(10, model))
+    }
+}
+
+pub struct Day10;
+impl Solution for Day10 {
+    type Model = Vec<day10::Instruction>;
+
+    fn parse(input: &str) -> Result<Self::Model, String> {
+        day10::parse_program(input).map_err(|e| e.to_string())
+    }
+    fn part1(model: &Self::Model) -> Result<String, String> {
+        let mut pgm = model.iter().cloned();
+        Ok(day10::cpu::CPU::new(&mut pgm).run()?.to_string())
+    }
+    fn part2(model: &Self::Model) -> Result<String, String> {
+        let mut pgm = model.iter().cloned();
+        let mut cpu = day10::cpu::CPU::new(&mut pgm);
+        cpu.run()?;
+        Ok(cpu.screen())
+    }
+}
+
+pub struct Day11;
+impl Solution for Day11 {
+    type Model = Vec<day11::Monkey>;
+
+    // Day 11 input is still hardcoded in the solution.
+    fn parse(_input: &str) -> Result<Self::Model, String> {
+        Ok(day11::puzzle_monkeys())
+    }
+    fn part1(_model: &Self::Model) -> Result<String, String> {
+        Err("Worry levels cannot be divided by 3 yet".to_string())
+    }
+    fn part2(model: &Self::Model) -> Result<String, String> {
+        let mut monkeys = model.clone();
+        for _ in 0..10000 {
+            day11::run_round(&mut monkeys);
+        }
+        Ok(day11::monkey_business(&monkeys).to_string())
+    }
+}
@@ -0,0 +1,39 @@
+use crate::days::{Day, FIRST_YEAR};
+use serde::Serialize;
+use std::error::Error;
+use std::fmt::Debug;
+
+fn show<T: Serialize + Debug>(model: &T, json: bool) -> Result<String, Box<dyn Error>> {
+    if json {
+        Ok(serde_json::to_string_pretty(model)?)
+    } else {
+        Ok(format!("{model:#?}"))
+    }
+}
+
+/// Parse the input of a day and render the resulting model, as JSON or with `Debug`.
+pub fn dump(day: &Day, input: &str, json: bool) -> Result<String, Box<dyn Error>> {
+    match (day.year, day.day) {
+        (FIRST_YEAR, 1) => show(&day1a::parse_diets(input)?, json),
+        (FIRST_YEAR, 2) => show(&day2::parse_guide(input)?, json),
+        (FIRST_YEAR, 5) => show(&day5::parse(input)?, json),
+        (FIRST_YEAR, 7) => show(&day7::parse_transcript(input)?, json),
+        (FIRST_YEAR, 8) => show(&day8::Garden::try_from(input)?, json),
+        (FIRST_YEAR, 9) => show(&day9::parse_moves(input)?, json),
+        (FIRST_YEAR, 10) => show(&day10::parse_program(input)?, json),
+        // Day 11 input is still hardcoded in the solution.
+        (FIRST_YEAR, 11) => show(&day11::puzzle_monkeys(), json),
+        (year, n) => Err(format!("Day {n} of {year} does not have a parsed model to dump.").into()),
+    }
+}
+
+#[test]
+fn dump_day5_json() {
+    let day5 = crate::days::find(FIRST_YEAR, 5).unwrap();
+    let json = dump(day5, include_str!("../../day5/test"), true).unwrap();
+    let v: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(v[0]["stacks"][1], serde_json::json!(['M', 'C', 'D']));
+    assert_eq!(v[1][3]["qty"], 1);
+    let day4 = crate::days::find(FIRST_YEAR, 4).unwrap();
+    assert!(dump(day4, "", true).is_err());
+}
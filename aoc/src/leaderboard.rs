@@ -0,0 +1,321 @@
+//! Statistics of a private leaderboard, from the JSON export of adventofcode.com.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The export, as served by `/{year}/leaderboard/private/view/{id}.json`.
+#[derive(Debug, Deserialize)]
+pub struct Export {
+    pub event: String,
+    pub members: BTreeMap<String, Member>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u32,
+    /// Day, then part, then when the star was earned.
+    pub completion_day_level: BTreeMap<u32, BTreeMap<u32, Star>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Star {
+    pub get_star_ts: u64,
+}
+
+impl Member {
+    fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+}
+
+/// How to rank the members.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Rule {
+    /// The leaderboard's own: for each star, one point per member who got it later or never.
+    Local,
+    /// Most stars, ties broken by who got their last star first.
+    Stars,
+    /// Most stars, ties broken by the smallest total time since the puzzles unlocked.
+    Time,
+    /// Smallest average time between part 1 and part 2.
+    Delta,
+}
+
+impl TryFrom<&str> for Rule {
+    type Error = String;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s {
+            "local" => Ok(Rule::Local),
+            "stars" => Ok(Rule::Stars),
+            "time" => Ok(Rule::Time),
+            "delta" => Ok(Rule::Delta),
+            _ => Err(format!(
+                "Unknown rule {s}, expected local, stars, time or delta"
+            )),
+        }
+    }
+}
+
+/// Seconds since the epoch of midnight UTC, using Howard Hinnant's `days_from_civil`.
+fn epoch_day(year: i64, month: i64, day: i64) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    (era * 146097 + doe - 719468) * 86400
+}
+
+/// Puzzles unlock at midnight EST, 05:00 UTC.
+pub fn unlock_ts(year: i64, day: u32) -> u64 {
+    (epoch_day(year, 12, day as i64) + 5 * 3600) as u64
+}
+
+/// How long a member took for one day, in seconds since the puzzle unlocked.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct DayTimes {
+    pub day: u32,
+    pub part1: Option<u64>,
+    pub part2: Option<u64>,
+    /// Time between the two stars.
+    pub delta: Option<u64>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct MemberStats {
+    pub rank: usize,
+    pub name: String,
+    pub stars: u32,
+    pub score: u64,
+    pub local_score: u32,
+    pub days: Vec<DayTimes>,
+}
+
+impl MemberStats {
+    fn total_time(&self) -> u64 {
+        self.days
+            .iter()
+            .flat_map(|d| [d.part1, d.part2])
+            .flatten()
+            .sum()
+    }
+
+    /// Average time between the two parts, if any day has both.
+    pub fn mean_delta(&self) -> Option<u64> {
+        let deltas = self
+            .days
+            .iter()
+            .filter_map(|d| d.delta)
+            .collect::<Vec<u64>>();
+        (!deltas.is_empty()).then(|| deltas.iter().sum::<u64>() / deltas.len() as u64)
+    }
+}
+
+/// Points of every member for every star: one per member who got it later or never.
+fn local_scores(export: &Export) -> BTreeMap<&str, u64> {
+    let n = export.members.len() as u64;
+    let mut scores = export
+        .members
+        .keys()
+        .map(|k| (k.as_str(), 0))
+        .collect::<BTreeMap<&str, u64>>();
+    for day in 1..=25 {
+        for part in 1..=2 {
+            let mut times = export
+                .members
+                .iter()
+                .filter_map(|(k, m)| Some((m.completion_day_level.get(&day)?.get(&part)?, k)))
+                .map(|(star, k)| (star.get_star_ts, k.as_str()))
+                .collect::<Vec<_>>();
+            times.sort();
+            for (position, (_, k)) in times.iter().enumerate() {
+                *scores.get_mut(k).unwrap() += n - position as u64;
+            }
+        }
+    }
+    scores
+}
+
+/// Per member stars, times and score under `rule`, best first.
+pub fn stats(export: &Export, rule: Rule) -> Result<Vec<MemberStats>, String> {
+    let year = export
+        .event
+        .parse::<i64>()
+        .map_err(|_| format!("Invalid event {}", export.event))?;
+    let local = local_scores(export);
+
+    let mut stats = export
+        .members
+        .iter()
+        .map(|(key, m)| {
+            let days = m
+                .completion_day_level
+                .iter()
+                .map(|(&day, parts)| {
+                    let unlock = unlock_ts(year, day);
+                    let time = |part| {
+                        parts
+                            .get(&part)
+                            .map(|s: &Star| s.get_star_ts.saturating_sub(unlock))
+                    };
+                    let (part1, part2) = (time(1), time(2));
+                    DayTimes {
+                        day,
+                        part1,
+                        part2,
+                        delta: part1.zip(part2).map(|(a, b)| b.saturating_sub(a)),
+                    }
+                })
+                .collect();
+            let last_star = m
+                .completion_day_level
+                .values()
+                .flat_map(|parts| parts.values().map(|s| s.get_star_ts))
+                .max()
+                .unwrap_or(u64::MAX);
+            let mut s = MemberStats {
+                rank: 0,
+                name: m.display_name(),
+                stars: m.stars,
+                score: 0,
+                local_score: m.local_score,
+                days,
+            };
+            s.score = match rule {
+                Rule::Local => local[key.as_str()],
+                Rule::Stars => s.stars as u64,
+                Rule::Time => s.total_time(),
+                Rule::Delta => s.mean_delta().unwrap_or(u64::MAX),
+            };
+            (s, last_star)
+        })
+        .collect::<Vec<(MemberStats, u64)>>();
+
+    stats.sort_by(|(a, a_last), (b, b_last)| match rule {
+        Rule::Local => b.score.cmp(&a.score),
+        Rule::Stars => b.stars.cmp(&a.stars).then(a_last.cmp(b_last)),
+        Rule::Time => b.stars.cmp(&a.stars).then(a.score.cmp(&b.score)),
+        Rule::Delta => a.score.cmp(&b.score),
+    });
+    Ok(stats
+        .into_iter()
+        .enumerate()
+        .map(|(i, (mut s, _))| {
+            s.rank = i + 1;
+            s
+        })
+        .collect())
+}
+
+/// `1h02m03s` or `-` when missing.
+fn hms(seconds: Option<u64>) -> String {
+    match seconds {
+        Some(s) if s >= 24 * 3600 => format!(">{}d", s / (24 * 3600)),
+        Some(s) => format!("{}h{:02}m{:02}s", s / 3600, s / 60 % 60, s % 60),
+        None => "-".to_string(),
+    }
+}
+
+pub fn print_table(stats: &[MemberStats], rule: Rule) {
+    println!(
+        "{:>4}  {:<24} {:>5} {:>12} {:>6}",
+        "rank", "member", "stars", "score", "local"
+    );
+    for s in stats {
+        let score = match rule {
+            Rule::Time => hms(Some(s.score)),
+            Rule::Delta => hms(s.mean_delta()),
+            _ => s.score.to_string(),
+        };
+        println!(
+            "{:>4}  {:<24} {:>5} {:>12} {:>6}",
+            s.rank, s.name, s.stars, score, s.local_score
+        );
+    }
+    println!();
+    println!(
+        "{:<24} {:>3} {:>10} {:>10} {:>10}",
+        "member", "day", "part1", "part2", "delta"
+    );
+    for s in stats {
+        for d in &s.days {
+            println!(
+                "{:<24} {:>3} {:>10} {:>10} {:>10}",
+                s.name,
+                d.day,
+                hms(d.part1),
+                hms(d.part2),
+                hms(d.delta)
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Day 1 of 2022 unlocked at 1669870800.
+    const EXPORT: &str = r#"{
+        "event": "2022",
+        "owner_id": 1,
+        "members": {
+            "1": {"id": 1, "name": "ada", "stars": 3, "local_score": 8, "global_score": 0,
+                  "last_star_ts": 1669957500,
+                  "completion_day_level": {
+                      "1": {"1": {"get_star_ts": 1669871100, "star_index": 0},
+                            "2": {"get_star_ts": 1669871400, "star_index": 1}},
+                      "2": {"1": {"get_star_ts": 1669957500, "star_index": 2}}}},
+            "2": {"id": 2, "name": null, "stars": 2, "local_score": 3, "global_score": 0,
+                  "last_star_ts": 1669871500,
+                  "completion_day_level": {
+                      "1": {"1": {"get_star_ts": 1669871000, "star_index": 0},
+                            "2": {"get_star_ts": 1669871500, "star_index": 1}}}}
+        }
+    }"#;
+
+    #[test]
+    fn unlock_times() {
+        assert_eq!(unlock_ts(2022, 1), 1669870800);
+        assert_eq!(unlock_ts(2022, 2), 1669870800 + 86400);
+    }
+
+    #[test]
+    fn rank_members() {
+        let export: Export = serde_json::from_str(EXPORT).unwrap();
+
+        let local = stats(&export, Rule::Local).unwrap();
+        assert_eq!(local[0].name, "ada");
+        // 1 + 2 for day 1, 2 for day 2 part 1
+        assert_eq!(local[0].score, 5);
+        assert_eq!(local[1].name, "(anonymous user #2)");
+        assert_eq!(local[1].score, 3);
+        assert_eq!(
+            local[0].days[0],
+            DayTimes {
+                day: 1,
+                part1: Some(300),
+                part2: Some(600),
+                delta: Some(300),
+            }
+        );
+
+        let delta = stats(&export, Rule::Delta).unwrap();
+        assert_eq!(delta[0].mean_delta(), Some(300));
+        assert_eq!(delta[0].rank, 1);
+
+        let time = stats(&export, Rule::Time).unwrap();
+        assert_eq!(time[0].name, "ada");
+        assert_eq!(
+            Rule::try_from("fastest"),
+            Err("Unknown rule fastest, expected local, stars, time or delta".to_string())
+        );
+    }
+}
@@ -0,0 +1,12 @@
+//! The registry of the solved days and how to run and measure them, shared by the runner and
+//! the fuzz targets.
+
+pub mod alloc;
+pub mod cache;
+pub mod days;
+pub mod solution;
+
+/// The runner counts allocations, so do its tests.
+#[cfg(test)]
+#[global_allocator]
+static GLOBAL: alloc::CountingAlloc = alloc::CountingAlloc;
@@ -0,0 +1,279 @@
+use std::env;
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
+
+use aoc::{alloc, cache, days, solution};
+
+mod batch;
+mod dump;
+mod leaderboard;
+mod perf;
+mod plugins;
+mod puzzle;
+mod sanitize;
+mod serve;
+mod status;
+
+#[global_allocator]
+static GLOBAL: alloc::CountingAlloc = alloc::CountingAlloc;
+
+const USAGE: &str = "Usage:
+  aoc run [day] [input] [--json] [--cache]
+                                     Solve one day, or all of them, with time and memory usage.
+                                     --cache reuses the models parsed by previous runs
+  aoc cache clear                    Remove the cached models
+  aoc dump <day> [input] [--json]    Print the parsed model of a day
+  aoc sanitize <day> [input] [--seed=N]
+                                     Print a shareable look-alike of an input (days 1, 3, 5, 6, 7)
+  aoc status [--no-tests]            Calendar of the solved days, checked against the answers
+                                     stored in `<input>.expected.json`, with their tests
+  aoc leaderboard <file.json> [--rule=local|stars|time|delta] [--json]
+                                     Statistics of a private leaderboard export
+  aoc show <day>                     Print the puzzle statement of a day
+  aoc search <term>                  Find the puzzle statements of any year mentioning a term
+  aoc batch <day> <dir>              Solve a day for every input of a folder, checking the answers
+                                     found in `<input>.expected.json` when present
+  aoc plugins [--plugins=DIR]        List the solver plugins (default folder: plugins)
+  aoc compare <day> [input] [--plugins=DIR]
+                                     Solve a day with the built-in solution and every plugin
+  aoc serve [--addr=HOST:PORT]       Answer `POST [/{year}]/day/{n}/part/{p}` with the input as
+                                     body (default 127.0.0.1:8080)
+  aoc perf report [--last=N] [--threshold=PCT]
+                                     Compare the latest run with the N before it (default 5, 20%)
+
+Days are those of the latest year, unless another is picked with --year=YYYY.";
+
+/// The input at `path`, or the puzzle input of the day.
+fn read_input(day: &days::Day, path: Option<&&String>) -> Result<String, String> {
+    let path = path
+        .map(PathBuf::from)
+        .unwrap_or_else(|| day.default_input());
+    fs::read_to_string(&path).map_err(|e| format!("Unable to read {}: {e}", path.display()))
+}
+
+/// Value of a `--name=value` flag.
+fn flag_value<T: std::str::FromStr>(
+    flags: &[&String],
+    name: &str,
+    default: T,
+) -> Result<T, String> {
+    match flags
+        .iter()
+        .find_map(|f| f.strip_prefix(name)?.strip_prefix('='))
+    {
+        Some(value) => value
+            .parse()
+            .map_err(|_| format!("Invalid value for {name}: {value}")),
+        None => Ok(default),
+    }
+}
+
+fn main() -> Result<(), String> {
+    let args = env::args().skip(1).collect::<Vec<String>>();
+    let (flags, args): (Vec<&String>, Vec<&String>) =
+        args.iter().partition(|a| a.starts_with("--"));
+    let json = flags.iter().any(|f| *f == "--json");
+    let cache = flags.iter().any(|f| *f == "--cache");
+    let year = flag_value(&flags, "--year", days::latest_year())?;
+    let find_day = |arg: Option<&&String>| -> Result<&days::Day, String> {
+        days::find(year, arg.ok_or(USAGE)?.parse().map_err(|_| USAGE)?)
+    };
+
+    match args.first().map(|s| s.as_str()) {
+        Some("dump") => {
+            let day = find_day(args.get(1))?;
+            let input = read_input(day, args.get(2))?;
+            println!(
+                "{}",
+                dump::dump(day, &input, json).map_err(|e| e.to_string())?
+            );
+            Ok(())
+        }
+        Some("run") => {
+            let selected = match args.get(1) {
+                Some(_) => vec![find_day(args.get(1))?],
+                None => days::of_year(year).collect(),
+            };
+            let mut reports = vec![];
+            for day in selected {
+                let input = read_input(day, args.get(2))?;
+                let report = if cache {
+                    (day.run_cached)(day.year, day.day, &input, Path::new(cache::CACHE_DIR))
+                } else {
+                    (day.run)(day.year, day.day, &input)
+                };
+                if !json {
+                    report.print();
+                }
+                reports.push(report);
+            }
+            perf::record(Path::new(perf::HISTORY_FILE), &reports)?;
+            if json {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&reports).map_err(|e| e.to_string())?
+                );
+            }
+            Ok(())
+        }
+        Some("sanitize") => {
+            let day = find_day(args.get(1))?;
+            if day.year != days::FIRST_YEAR {
+                return Err(format!("There are no sanitizers for {} yet.", day.year));
+            }
+            let input = read_input(day, args.get(2))?;
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map_or(0, |d| d.as_nanos() as u64);
+            let seed = flag_value(&flags, "--seed", now)?;
+            print!("{}", sanitize::sanitize(day.day, &input, seed)?);
+            Ok(())
+        }
+        Some("status") => {
+            let run_tests = !flags.iter().any(|f| *f == "--no-tests");
+            let status = days::of_year(year)
+                .map(|day| status::check(day, run_tests))
+                .collect::<Vec<_>>();
+            status::print_calendar(year, &status);
+            Ok(())
+        }
+        Some("leaderboard") => {
+            let path = args.get(1).ok_or(USAGE)?;
+            let export =
+                fs::read_to_string(path).map_err(|e| format!("Unable to read {path}: {e}"))?;
+            let export = serde_json::from_str(&export)
+                .map_err(|e| format!("Invalid leaderboard {path}: {e}"))?;
+            let rule = leaderboard::Rule::try_from(
+                flag_value(&flags, "--rule", "local".to_string())?.as_str(),
+            )?;
+            let stats = leaderboard::stats(&export, rule)?;
+            if json {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&stats).map_err(|e| e.to_string())?
+                );
+            } else {
+                leaderboard::print_table(&stats, rule);
+            }
+            Ok(())
+        }
+        Some("show") => {
+            let path = puzzle::path(Path::new(find_day(args.get(1))?.folder))?;
+            let statement = fs::read_to_string(&path)
+                .map_err(|e| format!("Unable to read {}: {e}", path.display()))?;
+            if puzzle::is_markdown(&path) {
+                print!("{}", puzzle::render(&statement, io::stdout().is_terminal()));
+            } else {
+                print!("{statement}");
+            }
+            Ok(())
+        }
+        Some("search") => {
+            let term = args[1..]
+                .iter()
+                .map(|s| s.as_str())
+                .collect::<Vec<&str>>()
+                .join(" ");
+            if term.is_empty() {
+                return Err(USAGE.to_string());
+            }
+            let hits = puzzle::search(Path::new("."), &term)?;
+            for hit in &hits {
+                println!("{} day{}:{}: {}", hit.year, hit.day, hit.line, hit.text);
+            }
+            if hits.is_empty() {
+                return Err(format!("No puzzle mentions \"{term}\""));
+            }
+            Ok(())
+        }
+        Some("cache") if args.get(1).map(|s| s.as_str()) == Some("clear") => {
+            let removed = cache::clear(Path::new(cache::CACHE_DIR))?;
+            println!("Removed {removed} cached model(s)");
+            Ok(())
+        }
+        Some("batch") => {
+            let day = find_day(args.get(1))?;
+            let dir = args.get(2).ok_or(USAGE)?;
+            let entries = batch::batch(day, Path::new(dir))?;
+            batch::print_table(&entries);
+            let failures = entries
+                .iter()
+                .filter(|e| e.part1.failed() || e.part2.failed())
+                .count();
+            if failures > 0 {
+                return Err(format!("{failures} of {} input(s) failed", entries.len()));
+            }
+            Ok(())
+        }
+        Some("plugins") => {
+            let dir = flag_value(&flags, "--plugins", plugins::PLUGIN_DIR.to_string())?;
+            for plugin in plugins::discover(Path::new(&dir)) {
+                match plugin {
+                    Ok(p) => println!(
+                        "{} Day {:<3} {:<24} {}",
+                        p.year,
+                        p.day,
+                        p.name,
+                        p.path.display()
+                    ),
+                    Err(e) => eprintln!("Skipped {e}"),
+                }
+            }
+            Ok(())
+        }
+        Some("compare") => {
+            let day = find_day(args.get(1))?;
+            let input = read_input(day, args.get(2))?;
+            let dir = flag_value(&flags, "--plugins", plugins::PLUGIN_DIR.to_string())?;
+
+            println!("built-in");
+            let expected = (day.run)(day.year, day.day, &input);
+            expected.print();
+            let mut disagree = 0;
+            for plugin in plugins::discover(Path::new(&dir)) {
+                let plugin = match plugin {
+                    Ok(p) if (p.year, p.day) == (day.year, day.day) => p,
+                    Ok(_) => continue,
+                    Err(e) => {
+                        eprintln!("Skipped {e}");
+                        continue;
+                    }
+                };
+                println!("\n{} ({})", plugin.name, plugin.path.display());
+                let report = plugin.run(&input);
+                report.print();
+                let answers = |r: &solution::Report| {
+                    r.steps.iter().map(|m| m.answer.clone()).collect::<Vec<_>>()
+                };
+                if answers(&report) != answers(&expected) {
+                    println!("Answers differ from the built-in solution");
+                    disagree += 1;
+                }
+            }
+            if disagree > 0 {
+                return Err(format!("{disagree} plugin(s) disagree"));
+            }
+            Ok(())
+        }
+        Some("serve") => serve::serve(&flag_value(&flags, "--addr", "127.0.0.1:8080".to_string())?),
+        Some("perf") if args.get(1).map(|s| s.as_str()) == Some("report") => {
+            let last = flag_value(&flags, "--last", 5)?;
+            let threshold = flag_value(&flags, "--threshold", 20.0)?;
+            let runs = perf::load(Path::new(perf::HISTORY_FILE))?;
+            if runs.is_empty() {
+                return Err("No runs recorded yet, try `aoc run` first.".to_string());
+            }
+            let trends = perf::trends(&runs, last, threshold);
+            perf::print_trends(&trends);
+            let regressions = trends.iter().filter(|t| t.regressed).count();
+            if regressions > 0 {
+                return Err(format!(
+                    "{regressions} step(s) regressed by more than {threshold}%"
+                ));
+            }
+            Ok(())
+        }
+        _ => Err(USAGE.to_string()),
+    }
+}
@@ -0,0 +1,256 @@
+//! Keep the timing and memory of every run so we can spot regressions between commits.
+
+use crate::alloc::human_bytes;
+use crate::solution::Report;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Where runs are appended, one JSON object per line.
+pub const HISTORY_FILE: &str = "perf_history.jsonl";
+
+/// One invocation of `aoc run`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Run {
+    pub commit: String,
+    /// Seconds since the epoch.
+    pub timestamp: u64,
+    pub reports: Vec<Report>,
+}
+
+/// Short hash of the commit we are running, with a `-dirty` suffix for uncommitted changes.
+pub fn current_commit() -> String {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+    };
+    match git(&["rev-parse", "--short", "HEAD"]) {
+        Some(hash) => match git(&["status", "--porcelain", "--untracked-files=no"]) {
+            Some(status) if !status.is_empty() => format!("{hash}-dirty"),
+            _ => hash,
+        },
+        None => "unknown".to_string(),
+    }
+}
+
+/// Append a run to the history.
+pub fn record(path: &Path, reports: &[Report]) -> Result<(), String> {
+    let run = Run {
+        commit: current_commit(),
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs()),
+        reports: reports.to_vec(),
+    };
+    let line = serde_json::to_string(&run).map_err(|e| e.to_string())?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| format!("Unable to open {}: {e}", path.display()))?;
+    writeln!(file, "{line}").map_err(|e| e.to_string())
+}
+
+/// All the runs recorded so far, oldest first.
+pub fn load(path: &Path) -> Result<Vec<Run>, String> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(_) => return Ok(vec![]),
+    };
+    content
+        .lines()
+        .enumerate()
+        .map(|(n, l)| {
+            serde_json::from_str(l).map_err(|e| format!("{}:{}: {e}", path.display(), n + 1))
+        })
+        .collect()
+}
+
+/// How the latest measurement of a step compares with the runs before it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trend {
+    pub year: u32,
+    pub day: u32,
+    pub step: String,
+    pub commit: String,
+    pub times: Vec<Duration>,
+    pub latest: Duration,
+    pub baseline: Duration,
+    pub latest_peak: usize,
+    pub baseline_peak: usize,
+    pub regressed: bool,
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+/// Compare the last run of every day/step with the average of up to `last` runs before it. A
+/// step regressed when its time or peak memory grew by more than `threshold` percent.
+pub fn trends(runs: &[Run], last: usize, threshold: f64) -> Vec<Trend> {
+    let mut keys: Vec<(u32, u32, String)> = runs
+        .iter()
+        .flat_map(|r| r.reports.iter())
+        .flat_map(|r| r.steps.iter().map(move |m| (r.year, r.day, m.step.clone())))
+        .collect();
+    keys.sort();
+    keys.dedup();
+
+    keys.into_iter()
+        .filter_map(|(year, day, step)| {
+            // (commit, measurement) for every run that measured this step
+            let history = runs
+                .iter()
+                .filter_map(|run| {
+                    let report = run
+                        .reports
+                        .iter()
+                        .find(|r| r.year == year && r.day == day)?;
+                    let m = report.steps.iter().find(|m| m.step == step)?;
+                    Some((&run.commit, m))
+                })
+                .collect::<Vec<_>>();
+            let ((commit, latest), before) = history.split_last()?;
+            let before = &before[before.len().saturating_sub(last)..];
+
+            let (baseline, baseline_peak) = if before.is_empty() {
+                (latest.time, latest.memory.peak)
+            } else {
+                let times = before.iter().map(|(_, m)| m.time.as_secs_f64());
+                let peaks = before.iter().map(|(_, m)| m.memory.peak as f64);
+                (
+                    Duration::from_secs_f64(mean(&times.collect::<Vec<f64>>())),
+                    mean(&peaks.collect::<Vec<f64>>()) as usize,
+                )
+            };
+            let grew = |now: f64, then: f64| then > 0.0 && (now - then) / then * 100.0 > threshold;
+
+            Some(Trend {
+                year,
+                day,
+                step,
+                commit: commit.to_string(),
+                times: before
+                    .iter()
+                    .map(|(_, m)| m.time)
+                    .chain([latest.time])
+                    .collect(),
+                latest: latest.time,
+                baseline,
+                latest_peak: latest.memory.peak,
+                baseline_peak,
+                regressed: grew(latest.time.as_secs_f64(), baseline.as_secs_f64())
+                    || grew(latest.memory.peak as f64, baseline_peak as f64),
+            })
+        })
+        .collect()
+}
+
+/// A tiny chart of the values, eg: `▁▂▂▇`.
+fn sparkline(times: &[Duration]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let max = times
+        .iter()
+        .max()
+        .copied()
+        .unwrap_or_default()
+        .as_secs_f64();
+    times
+        .iter()
+        .map(|t| {
+            if max == 0.0 {
+                BARS[0]
+            } else {
+                BARS[((t.as_secs_f64() / max) * (BARS.len() - 1) as f64).round() as usize]
+            }
+        })
+        .collect()
+}
+
+fn change(now: f64, then: f64) -> String {
+    if then == 0.0 {
+        "   n/a".to_string()
+    } else {
+        format!("{:+5.0}%", (now - then) / then * 100.0)
+    }
+}
+
+pub fn print_trends(trends: &[Trend]) {
+    for t in trends {
+        println!(
+            "{} Day {:<3} {:<6} {:<12} {:>10.3?} {} {:>10} {} {}{}",
+            t.year,
+            t.day,
+            t.step,
+            sparkline(&t.times),
+            t.latest,
+            change(t.latest.as_secs_f64(), t.baseline.as_secs_f64()),
+            human_bytes(t.latest_peak),
+            change(t.latest_peak as f64, t.baseline_peak as f64),
+            t.commit,
+            if t.regressed { "  REGRESSION" } else { "" }
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alloc::MemStats;
+    use crate::solution::Measurement;
+
+    fn run(commit: &str, millis: u64, peak: usize) -> Run {
+        Run {
+            commit: commit.to_string(),
+            timestamp: 0,
+            reports: vec![Report {
+                year: 2022,
+                day: 8,
+                steps: vec![Measurement {
+                    step: "part1".to_string(),
+                    answer: Ok("1829".to_string()),
+                    time: Duration::from_millis(millis),
+                    memory: MemStats {
+                        allocations: 1,
+                        bytes: peak,
+                        peak,
+                    },
+                }],
+            }],
+        }
+    }
+
+    #[test]
+    fn flag_slower_runs() {
+        let runs = vec![run("a", 10, 100), run("b", 12, 100), run("c", 20, 100)];
+        let t = trends(&runs, 5, 20.0);
+        assert_eq!(t.len(), 1);
+        assert_eq!(t[0].baseline, Duration::from_millis(11));
+        assert_eq!(t[0].commit, "c");
+        assert!(t[0].regressed);
+
+        // Only compare with the run just before
+        let t = trends(&runs[..2], 1, 25.0);
+        assert!(!t[0].regressed);
+    }
+
+    #[test]
+    fn runs_before_years_are_from_2022() {
+        let line = r#"{"commit":"c2d8618","timestamp":0,"reports":[{"day":1,"steps":[]}]}"#;
+        let run: Run = serde_json::from_str(line).unwrap();
+        assert_eq!(run.reports[0].year, 2022);
+    }
+
+    #[test]
+    fn flag_memory_growth() {
+        let runs = vec![run("a", 10, 100), run("b", 10, 200)];
+        assert!(trends(&runs, 5, 20.0)[0].regressed);
+    }
+}
@@ -0,0 +1,139 @@
+//! Alternative solutions loaded from shared libraries, see the `plugin` crate.
+//!
+//! Plugins allocate with their own allocator, so the memory columns of their reports only count
+//! the copies of the answers made by the runner: only the timings compare with the built-in
+//! solutions.
+
+use crate::solution::{run_with, Report};
+use libloading::Library;
+use plugin::{Outcome, PluginV2, ABI_VERSION, ENTRY_POINT};
+use std::env::consts::DLL_EXTENSION;
+use std::ffi::{c_void, CStr};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Folder searched for plugins by default.
+pub const PLUGIN_DIR: &str = "plugins";
+
+/// A loaded plugin.
+pub struct Plugin {
+    pub path: PathBuf,
+    pub name: String,
+    pub year: u32,
+    pub day: u32,
+    vtable: *const PluginV2,
+    // Must outlive `vtable`.
+    _library: Library,
+}
+
+/// A model owned by a plugin, released when dropped.
+struct Model<'a> {
+    plugin: &'a PluginV2,
+    ptr: *mut c_void,
+}
+
+impl Drop for Model<'_> {
+    fn drop(&mut self) {
+        // SAFETY: the model was parsed by this plugin and is dropped once.
+        unsafe { (self.plugin.free_model)(self.ptr) };
+    }
+}
+
+impl Plugin {
+    /// Load the library at `path` and check it speaks our ABI.
+    pub fn load(path: &Path) -> Result<Plugin, String> {
+        let fail = |e: &dyn std::fmt::Display| format!("{}: {e}", path.display());
+        // SAFETY: loading runs the initialisers of the library, we trust the plugins folder.
+        let library = unsafe { Library::new(path) }.map_err(|e| fail(&e))?;
+        // SAFETY: the entry point has this signature in every ABI version.
+        let vtable = unsafe {
+            let entry = library
+                .get::<extern "C" fn() -> *const PluginV2>(ENTRY_POINT)
+                .map_err(|e| fail(&e))?;
+            entry()
+        };
+        // SAFETY: the entry point returns a pointer to a static.
+        let v = unsafe { &*vtable };
+        if v.abi_version != ABI_VERSION {
+            return Err(fail(&format!(
+                "built for plugin ABI {}, expected {ABI_VERSION}",
+                v.abi_version
+            )));
+        }
+        // SAFETY: `name` is a nul terminated static string.
+        let name = unsafe { CStr::from_ptr(v.name) }
+            .to_string_lossy()
+            .into_owned();
+        Ok(Plugin {
+            path: path.to_path_buf(),
+            name,
+            year: v.year,
+            day: v.day,
+            vtable,
+            _library: library,
+        })
+    }
+
+    fn vtable(&self) -> &PluginV2 {
+        // SAFETY: checked in `load`, and the library is kept loaded as long as `self`.
+        unsafe { &*self.vtable }
+    }
+
+    /// Copy an outcome of the plugin and release it.
+    fn read(&self, outcome: Outcome) -> Result<String, String> {
+        // SAFETY: the text was just returned by the plugin, and is released once.
+        let text = unsafe { outcome.text.to_string() };
+        unsafe { (self.vtable().free_text)(outcome.text) };
+        if outcome.ok {
+            Ok(text)
+        } else {
+            Err(text)
+        }
+    }
+
+    /// Parse the input and run both parts, measuring each step.
+    pub fn run(&self, input: &str) -> Report {
+        let v = self.vtable();
+        run_with(
+            self.year,
+            self.day,
+            || {
+                let mut ptr = std::ptr::null_mut();
+                // SAFETY: `input` outlives the call, `ptr` is set when parsing succeeds.
+                self.read(unsafe { (v.parse)(input.as_ptr(), input.len(), &mut ptr) })?;
+                Ok(Model { plugin: v, ptr })
+            },
+            // SAFETY: the model comes from this plugin and is alive.
+            |m| self.read(unsafe { (v.part1)(m.ptr) }),
+            |m| self.read(unsafe { (v.part2)(m.ptr) }),
+        )
+    }
+}
+
+/// Load every library of `dir`, sorted by file name. A missing folder has no plugins.
+pub fn discover(dir: &Path) -> Vec<Result<Plugin, String>> {
+    let mut paths = match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| p.extension().is_some_and(|ext| ext == DLL_EXTENSION))
+            .collect::<Vec<PathBuf>>(),
+        Err(_) => vec![],
+    };
+    paths.sort();
+    paths.iter().map(|p| Plugin::load(p)).collect()
+}
+
+#[test]
+fn reject_libraries_that_are_not_plugins() {
+    let dir = std::env::temp_dir().join(format!("aoc-plugins-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join(format!("fake.{DLL_EXTENSION}")), "not a library").unwrap();
+    fs::write(dir.join("notes.txt"), "ignored").unwrap();
+
+    let plugins = discover(&dir);
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(plugins.len(), 1);
+    assert!(plugins[0].is_err());
+    assert!(discover(Path::new("no such folder")).is_empty());
+}
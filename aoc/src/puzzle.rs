@@ -0,0 +1,204 @@
+//! Read the puzzle statements committed next to each day, without a browser.
+
+use crate::days;
+use pulldown_cmark::{CodeBlockKind, Event, Parser, Tag, TagEnd};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const BOLD: &str = "\x1b[1m";
+const UNDERLINE: &str = "\x1b[4m";
+const YELLOW: &str = "\x1b[33m";
+const GREEN: &str = "\x1b[32m";
+const CYAN: &str = "\x1b[36m";
+const RESET: &str = "\x1b[0m";
+
+/// Terminal styles, or nothing at all when writing to a file.
+struct Style {
+    color: bool,
+}
+
+impl Style {
+    fn on(&self, codes: &[&str]) -> String {
+        if self.color {
+            codes.concat()
+        } else {
+            String::new()
+        }
+    }
+    fn off(&self) -> &'static str {
+        if self.color {
+            RESET
+        } else {
+            ""
+        }
+    }
+}
+
+/// The puzzle statement of a day, eg: `day5/puzzle.md`, or its `README.*` for the early days.
+pub fn path(dir: &Path) -> Result<PathBuf, String> {
+    let puzzle = dir.join("puzzle.md");
+    if puzzle.is_file() {
+        return Ok(puzzle);
+    }
+    let mut readmes = fs::read_dir(dir)
+        .map_err(|e| format!("Unable to read {}: {e}", dir.display()))?
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|p| p.is_file() && p.file_stem().is_some_and(|stem| stem == "README"))
+        .collect::<Vec<_>>();
+    readmes.sort();
+    readmes
+        .into_iter()
+        .next()
+        .ok_or_else(|| format!("No statement for this day: {} is missing", puzzle.display()))
+}
+
+/// True if the statement at `path` is Markdown, to be rendered rather than printed as is.
+pub fn is_markdown(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "md")
+}
+
+/// Render Markdown for the terminal: headings stand out, emphasized text (the important bits
+/// of a statement) is highlighted and code blocks are indented.
+pub fn render(markdown: &str, color: bool) -> String {
+    let style = Style { color };
+    let mut out = String::new();
+    let mut in_code_block = false;
+    let mut list_depth = 0;
+
+    for event in Parser::new(markdown) {
+        match event {
+            Event::Start(Tag::Heading { .. }) => out += &style.on(&[BOLD, UNDERLINE]),
+            Event::End(TagEnd::Heading(_)) => {
+                out += style.off();
+                out += "\n\n";
+            }
+            Event::Start(Tag::Emphasis) => out += &style.on(&[BOLD, YELLOW]),
+            Event::Start(Tag::Strong) => out += &style.on(&[BOLD]),
+            Event::End(TagEnd::Emphasis | TagEnd::Strong) => out += style.off(),
+            Event::End(TagEnd::Paragraph) => out += "\n\n",
+            Event::Start(Tag::CodeBlock(kind)) => {
+                in_code_block = true;
+                if let CodeBlockKind::Fenced(lang) = kind {
+                    if !lang.is_empty() {
+                        out += &format!("    ({lang})\n");
+                    }
+                }
+                out += &style.on(&[GREEN]);
+            }
+            Event::End(TagEnd::CodeBlock) => {
+                in_code_block = false;
+                out += style.off();
+                out += "\n";
+            }
+            Event::Start(Tag::List(_)) => list_depth += 1,
+            Event::End(TagEnd::List(_)) => {
+                list_depth -= 1;
+                if list_depth == 0 {
+                    out += "\n";
+                }
+            }
+            Event::Start(Tag::Item) => {
+                out += &"  ".repeat(list_depth - 1);
+                out += "- ";
+            }
+            Event::End(TagEnd::Item) => out += "\n",
+            Event::Text(text) if in_code_block => {
+                for line in text.lines() {
+                    out += &format!("    {line}\n");
+                }
+            }
+            Event::Text(text) => out += &text,
+            Event::Code(code) => out += &format!("{}{code}{}", style.on(&[CYAN]), style.off()),
+            Event::SoftBreak => out += "\n",
+            Event::HardBreak => out += "\n",
+            Event::Rule => out += "----------\n\n",
+            _ => {}
+        }
+    }
+    out.trim_end().to_string() + "\n"
+}
+
+/// A line of a statement containing the searched term.
+#[derive(Debug, PartialEq)]
+pub struct Hit {
+    pub year: u32,
+    pub day: u32,
+    pub line: usize,
+    pub text: String,
+}
+
+/// Case-insensitive search of `term` in the statements of every registered day, `root` being
+/// the folder of the repository.
+pub fn search(root: &Path, term: &str) -> Result<Vec<Hit>, String> {
+    let term = term.to_lowercase();
+    let mut hits = vec![];
+    for day in days::DAYS {
+        let content = match path(&root.join(day.folder))
+            .and_then(|statement| fs::read_to_string(statement).map_err(|e| e.to_string()))
+        {
+            Ok(content) => content,
+            Err(_) => continue,
+        };
+        for (n, line) in content.lines().enumerate() {
+            if line.to_lowercase().contains(&term) {
+                hits.push(Hit {
+                    year: day.year,
+                    day: day.day,
+                    line: n + 1,
+                    text: line.trim().to_string(),
+                });
+            }
+        }
+    }
+    Ok(hits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_plain() {
+        let md = "\\--- Day 5: Supply Stacks ---\n----------\n\nStacks of *crates* like `Z`:\n\n```\n[Z] [M]\n 1   2\n```\n";
+        assert_eq!(
+            render(md, false),
+            "--- Day 5: Supply Stacks ---\n\nStacks of crates like Z:\n\n    [Z] [M]\n     1   2\n"
+        );
+    }
+
+    #[test]
+    fn render_styles() {
+        let rendered = render("What is *the answer*?", true);
+        assert_eq!(rendered, "What is \x1b[1m\x1b[33mthe answer\x1b[0m?\n");
+    }
+
+    #[test]
+    fn search_statements() {
+        let hits = search(Path::new(".."), "supply STACKS").unwrap();
+        assert!(!hits.is_empty());
+        assert!(hits.iter().all(|h| (h.year, h.day) == (2022, 5)));
+        assert!(search(Path::new(".."), "no puzzle mentions this")
+            .unwrap()
+            .is_empty());
+        // Day 2 only has a README.
+        let hits = search(Path::new(".."), "giant Rock Paper Scissors").unwrap();
+        assert!(hits.iter().any(|h| (h.year, h.day) == (2022, 2)));
+    }
+
+    #[test]
+    fn statement_paths() {
+        let root = Path::new("..");
+        assert_eq!(
+            path(&root.join("day5")).unwrap(),
+            root.join("day5/puzzle.md")
+        );
+        assert_eq!(
+            path(&root.join("day2")).unwrap(),
+            root.join("day2/README.txt")
+        );
+        assert!(!is_markdown(&root.join("day2/README.txt")));
+        assert!(is_markdown(&root.join("day3/README.md")));
+        let missing = path(&root.join("day1")).unwrap_err();
+        assert!(missing.contains("day1/puzzle.md is missing"), "{missing}");
+    }
+}
@@ -0,0 +1,190 @@
+//! Rewrite a personal puzzle input into a look-alike that can be shared, eg: to reproduce a bug.
+//!
+//! Each transformation keeps the structure the solutions rely on, so a bug seen on the original
+//! input shows on the sanitized one:
+//!
+//! - day 1: every calorie count is multiplied by the same factor, the ranking of the elves holds;
+//!   inputs that would overflow once scaled are refused
+//! - day 3: item letters are swapped for others, the shared items are the images of the old ones
+//! - day 5: crate labels are swapped for others, the stacks move the same way
+//! - day 6: letters are swapped for others, markers stay at the same positions
+//! - day 7: files and directories are renamed, spaces in their names included, the sizes of the
+//!   directories do not change
+
+use rng::Rng;
+use std::collections::HashMap;
+
+/// A random one to one mapping of `alphabet` onto itself.
+fn permutation(alphabet: &str, rng: &mut Rng) -> HashMap<char, char> {
+    let mut shuffled = alphabet.chars().collect::<Vec<char>>();
+    rng.shuffle(&mut shuffled);
+    alphabet.chars().zip(shuffled).collect()
+}
+
+fn map_chars(input: &str, mapping: &HashMap<char, char>) -> String {
+    input
+        .chars()
+        .map(|c| *mapping.get(&c).unwrap_or(&c))
+        .collect()
+}
+
+/// Scale every count, failing rather than letting an elf's scaled total overflow.
+fn day1(input: &str, rng: &mut Rng) -> Result<String, String> {
+    let factor = 2 + rng.below(8) as i64;
+    let mut total = 0i64;
+    let mut lines = vec![];
+    for (n, l) in input.lines().enumerate() {
+        match l.parse::<i64>() {
+            Ok(calories) => {
+                let scaled = calories
+                    .checked_mul(factor)
+                    .filter(|c| total.checked_add(*c).is_some())
+                    .ok_or(format!("line {}: scaling {calories} overflows", n + 1))?;
+                total += scaled;
+                lines.push(scaled.to_string());
+            }
+            Err(_) => {
+                total = 0;
+                lines.push(l.to_string());
+            }
+        }
+    }
+    Ok(lines.join("\n") + "\n")
+}
+
+fn day7(input: &str, rng: &mut Rng) -> String {
+    let mut names: HashMap<String, String> = HashMap::new();
+    let mut rename = |name: &str, prefix: &str| -> String {
+        let n = names.len();
+        names
+            .entry(name.to_string())
+            .or_insert_with(|| format!("{prefix}{n}"))
+            .clone()
+    };
+    // Start numbering somewhere random, so the names do not reveal the order of the listing.
+    for _ in 0..rng.below(100) {
+        rename(&format!("\0{}", rng.next_u64()), "");
+    }
+
+    // Names run to the end of the line, spaces included.
+    input
+        .lines()
+        .map(
+            |line| match line.splitn(2, ' ').collect::<Vec<&str>>()[..] {
+                ["$", command] => match command.strip_prefix("cd ") {
+                    Some(dir) if dir != "/" && dir != ".." => format!("$ cd {}", rename(dir, "d")),
+                    _ => line.to_string(),
+                },
+                ["dir", dir] => format!("dir {}", rename(dir, "d")),
+                [size, file] if size.parse::<u64>().is_ok() => {
+                    format!("{size} {}", rename(file, "f"))
+                }
+                _ => line.to_string(),
+            },
+        )
+        .collect::<Vec<String>>()
+        .join("\n")
+        + "\n"
+}
+
+/// Sanitize the input of a day of 2022, shuffling with `seed`.
+pub fn sanitize(day: u32, input: &str, seed: u64) -> Result<String, String> {
+    let mut rng = Rng::new(seed);
+    let lower = "abcdefghijklmnopqrstuvwxyz";
+    let upper = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+    match day {
+        1 => day1(input, &mut rng),
+        3 => Ok(map_chars(
+            input,
+            &permutation(&format!("{lower}{upper}"), &mut rng),
+        )),
+        5 => Ok(map_chars(input, &permutation(upper, &mut rng))),
+        6 => Ok(map_chars(input, &permutation(lower, &mut rng))),
+        7 => Ok(day7(input, &mut rng)),
+        _ => Err(format!("Day {day} has no sanitizer yet.")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::{Day5, Day6, Day7};
+    use crate::solution::Solution;
+
+    #[test]
+    fn same_seed_same_output() {
+        let input = "vJrwpWtwJgWrhcsFMMfFFhFp\n";
+        assert_eq!(sanitize(3, input, 7), sanitize(3, input, 7));
+        assert_ne!(sanitize(3, input, 7), sanitize(3, input, 8));
+        assert!(sanitize(8, input, 7).is_err());
+    }
+
+    #[test]
+    fn day1_keeps_the_ranking() {
+        let input = "1000\n2000\n\n4000\n\n5000\n6000\n";
+        let sanitized = day1(input, &mut Rng::new(1)).unwrap();
+        let diets = day1a::parse_diets(&sanitized).unwrap();
+        let factor = diets[0].total_calories / 3000;
+        assert_eq!(
+            diets.iter().map(|d| d.total_calories).collect::<Vec<_>>(),
+            vec![3000 * factor, 4000 * factor, 11000 * factor]
+        );
+
+        // Negative counts are scaled too.
+        let sanitized = day1("-1000\n3000\n\n1000\n", &mut Rng::new(1)).unwrap();
+        let diets = day1a::parse_diets(&sanitized).unwrap();
+        assert_eq!(diets[0].total_calories, 2 * diets[1].total_calories);
+        assert!(diets[0].items[0] < 0);
+    }
+
+    #[test]
+    fn day1_overflows() {
+        assert!(day1("9223372036854775807\n\n1\n", &mut Rng::new(1)).is_err());
+        // Every count fits, their scaled sum does not.
+        let half = i64::MAX / 2;
+        assert!(day1(&format!("{half}\n{half}\n"), &mut Rng::new(1)).is_err());
+        assert!(day1(&format!("{half}\n\n{half}\n"), &mut Rng::new(1)).is_err());
+    }
+
+    #[test]
+    fn day3_maps_the_shared_items() {
+        let line = "vJrwpWtwJgWrhcsFMMfFFhFp";
+        let mapping = permutation(
+            "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ",
+            &mut Rng::new(3),
+        );
+        let shared = day3::find_shared_item(line).unwrap();
+        let sanitized = map_chars(line, &mapping);
+        assert_eq!(day3::find_shared_item(&sanitized), Ok(mapping[&shared]));
+    }
+
+    #[test]
+    fn day5_moves_the_same_crates() {
+        let input = include_str!("../../day5/test");
+        let sanitized = sanitize(5, input, 5).unwrap();
+        let mapping = permutation("ABCDEFGHIJKLMNOPQRSTUVWXYZ", &mut Rng::new(5));
+        let dock = Day5::parse(&sanitized).unwrap();
+        let original = Day5::parse(input).unwrap();
+        for part in [Day5::part1, Day5::part2] {
+            let expected = map_chars(&part(&original).unwrap(), &mapping);
+            assert_eq!(part(&dock), Ok(expected));
+        }
+    }
+
+    #[test]
+    fn day6_and_day7_keep_their_answers() {
+        let input = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+        let sanitized = sanitize(6, input, 6).unwrap();
+        assert_eq!(Day6::part2(&sanitized), Ok("19".to_string()));
+
+        let input = include_str!("../../day7/test");
+        let sanitized = sanitize(7, input, 7).unwrap();
+        assert!(!sanitized.contains("dir a\n"));
+        let tree = Day7::parse(&sanitized).unwrap();
+        assert_eq!(Day7::part1(&tree), Ok("95437".to_string()));
+        assert_eq!(Day7::part2(&tree), Ok("24933642".to_string()));
+
+        let spaces = sanitize(7, "$ cd my documents\n$ ls\n12 tax return.pdf\n", 7).unwrap();
+        assert!(!spaces.contains("documents") && !spaces.contains("tax"));
+    }
+}
@@ -0,0 +1,371 @@
+//! A small HTTP server so other tools can call the solvers without shelling out.
+//!
+//! Requests are handled one at a time: the allocation counters are global and would mix the
+//! measurements of concurrent requests. A client that stops sending is dropped after
+//! `READ_TIMEOUT`. Solvers run on a worker thread: one that panics only fails its own request,
+//! and one still running after `SOLVE_TIMEOUT` is answered 504, then 503 until it is done.
+
+use crate::days::{self, Day};
+use crate::solution::{Measurement, Report};
+use serde::Serialize;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::Duration;
+
+/// Largest puzzle input accepted, in bytes.
+pub const MAX_BODY: usize = 16 << 20;
+
+/// How long to wait for a client to send its request.
+pub const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long a solver may run before its request gives up on it.
+pub const SOLVE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Stack of the worker threads, as deep as the one of the command line.
+const SOLVER_STACK: usize = 64 << 20;
+
+/// Runs the solvers off the accept thread, one at a time.
+#[derive(Debug, Clone)]
+pub struct Worker {
+    deadline: Duration,
+    /// Set while a solver runs, including one whose request already timed out.
+    busy: Arc<AtomicBool>,
+}
+
+impl Worker {
+    /// A worker answering 504 when a solver runs longer than `deadline`.
+    pub fn new(deadline: Duration) -> Worker {
+        Worker {
+            deadline,
+            busy: Arc::new(AtomicBool::new(false)),
+        }
+    }
+}
+
+/// Body of the response to `POST /day/{n}/part/{p}`.
+#[derive(Debug, Serialize)]
+pub struct Answer {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    /// How parsing went, its error is the diagnostic of an invalid input.
+    pub parse: Measurement,
+    /// Missing when the input could not be parsed.
+    pub solve: Option<Measurement>,
+}
+
+#[derive(Debug, Serialize)]
+struct Failure {
+    error: String,
+}
+
+/// Route a request, returning the HTTP status and the JSON body. Puzzles are either
+/// `/{year}/day/{n}/part/{p}` or `/day/{n}/part/{p}` for the latest year.
+pub fn handle(worker: &Worker, method: &str, path: &str, body: &str) -> (u16, String) {
+    let fail = |status, error: String| (status, to_json(&Failure { error }));
+
+    let segments = path.trim_matches('/').split('/').collect::<Vec<&str>>();
+    let latest = days::latest_year().to_string();
+    let (year, day, part) = match segments[..] {
+        ["day", day, "part", part] => (latest.as_str(), day, part),
+        [year, "day", day, "part", part] => (year, day, part),
+        _ => return fail(404, format!("No such route: {path}")),
+    };
+    let (year, day, part) = match (year.parse::<u32>(), day.parse::<u32>(), part.parse::<u32>()) {
+        (Ok(year), Ok(day), Ok(part @ (1 | 2))) => (year, day, part),
+        _ => return fail(404, format!("No such puzzle: {path}")),
+    };
+    if method != "POST" {
+        return fail(405, "Send the puzzle input with POST".to_string());
+    }
+    let day = match days::find(year, day) {
+        Ok(day) => day,
+        Err(e) => return fail(404, e),
+    };
+
+    solve(worker, day, part, body)
+}
+
+/// Solve `part` of `day` on a worker thread, a panic of the solver being an internal error.
+fn solve(worker: &Worker, day: &Day, part: u32, body: &str) -> (u16, String) {
+    let fail = |status, error: String| (status, to_json(&Failure { error }));
+
+    if worker.busy.swap(true, Ordering::SeqCst) {
+        return fail(503, "A previous solver is still running".to_string());
+    }
+    let (sender, receiver) = mpsc::channel();
+    let (busy, run_part, year, n, body) = (
+        worker.busy.clone(),
+        day.run_part,
+        day.year,
+        day.day,
+        body.to_string(),
+    );
+    let spawned = thread::Builder::new()
+        .name(format!("day{n}"))
+        .stack_size(SOLVER_STACK)
+        .spawn(move || {
+            let report = catch_unwind(AssertUnwindSafe(|| run_part(year, n, &body, part)));
+            // Free before answering, so that the next request finds the worker available.
+            busy.store(false, Ordering::SeqCst);
+            let _ = sender.send(report);
+        });
+    if let Err(e) = spawned {
+        worker.busy.store(false, Ordering::SeqCst);
+        return fail(500, format!("Unable to start the solver: {e}"));
+    }
+    let steps = match receiver.recv_timeout(worker.deadline) {
+        Ok(Ok(Report { steps, .. })) => steps,
+        Err(mpsc::RecvTimeoutError::Timeout) => {
+            let error = format!(
+                "The solver of day {n} took longer than {:?}",
+                worker.deadline
+            );
+            return fail(504, error);
+        }
+        _ => return fail(500, format!("The solver of day {n} panicked")),
+    };
+    let mut steps = steps.into_iter();
+    let parse = steps.next().expect("parse is always measured");
+    let solve = steps.find(|m| m.step == format!("part{part}"));
+    let status = match (&parse.answer, &solve) {
+        (Err(_), _) => 422,
+        (_, Some(m)) if m.answer.is_err() => 422,
+        _ => 200,
+    };
+    let answer = Answer {
+        year: day.year,
+        day: day.day,
+        part,
+        parse,
+        solve,
+    };
+    (status, to_json(&answer))
+}
+
+fn to_json<T: Serialize>(value: &T) -> String {
+    serde_json::to_string(value).unwrap_or_else(|e| format!(r#"{{"error":"{e}"}}"#))
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        422 => "Unprocessable Entity",
+        503 => "Service Unavailable",
+        504 => "Gateway Timeout",
+        _ => "Internal Server Error",
+    }
+}
+
+/// Read one request and write its response.
+fn respond(worker: &Worker, stream: TcpStream) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(&stream);
+    let mut request = String::new();
+    reader.read_line(&mut request)?;
+    let mut content_length = Some(0);
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse::<usize>().ok();
+            }
+        }
+    }
+    let (status, json) = match request.split_whitespace().collect::<Vec<&str>>()[..] {
+        _ if content_length.is_none() => (400, r#"{"error":"Invalid Content-Length"}"#.to_string()),
+        _ if content_length > Some(MAX_BODY) => (
+            413,
+            format!(r#"{{"error":"The input is larger than {MAX_BODY} bytes"}}"#),
+        ),
+        [method, path, _] => {
+            let mut body = vec![0; content_length.unwrap_or(0)];
+            reader.read_exact(&mut body)?;
+            match String::from_utf8(body) {
+                Ok(body) => handle(worker, method, path, &body),
+                Err(_) => (400, r#"{"error":"The input is not UTF-8"}"#.to_string()),
+            }
+        }
+        _ => (400, r#"{"error":"Malformed request"}"#.to_string()),
+    };
+    let mut stream = &stream;
+    write!(
+        stream,
+        "HTTP/1.1 {status} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{json}",
+        reason(status),
+        json.len()
+    )
+}
+
+/// Serve the solvers on `addr` until the process is stopped.
+pub fn serve(addr: &str) -> Result<(), String> {
+    let listener = TcpListener::bind(addr).map_err(|e| format!("Unable to bind {addr}: {e}"))?;
+    println!("Listening on http://{addr}");
+    let worker = Worker::new(SOLVE_TIMEOUT);
+    for stream in listener.incoming() {
+        if let Err(e) = stream.and_then(|stream| respond(&worker, stream)) {
+            eprintln!("{e}");
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn worker() -> Worker {
+        Worker::new(SOLVE_TIMEOUT)
+    }
+
+    #[test]
+    fn solve_day4() {
+        let (status, json) = handle(&worker(), "POST", "/day/4/part/2", "2-4,6-8\n5-7,7-9\n");
+        assert_eq!(status, 200);
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["solve"]["answer"]["Ok"], "1");
+        assert_eq!(value["parse"]["step"], "parse");
+
+        let (status, json) = handle(
+            &worker(),
+            "POST",
+            "/2022/day/4/part/2",
+            "2-4,6-8\n5-7,7-9\n",
+        );
+        assert_eq!(status, 200);
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["year"], 2022);
+    }
+
+    #[test]
+    fn report_parse_errors() {
+        let (status, json) = handle(&worker(), "POST", "/day/4/part/1", "2-4;6-8\n");
+        assert_eq!(status, 422);
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert!(value["parse"]["answer"]["Err"]
+            .as_str()
+            .unwrap()
+            .starts_with("line 1, column 4"));
+        assert!(value["solve"].is_null());
+    }
+
+    #[test]
+    fn survive_panics() {
+        let day = Day {
+            run_part: |_, _, _, _| panic!("boom"),
+            ..*days::find(2022, 4).unwrap()
+        };
+        let (status, json) = solve(&worker(), &day, 1, "");
+        assert_eq!(status, 500);
+        assert!(json.contains("panicked"));
+        assert_eq!(
+            handle(&worker(), "POST", "/day/4/part/1", "2-4,6-8\n").0,
+            200
+        );
+    }
+
+    #[test]
+    fn give_up_on_slow_solvers() {
+        let slow = Day {
+            run_part: |_, _, _, _| {
+                thread::sleep(Duration::from_millis(300));
+                panic!("too late")
+            },
+            ..*days::find(2022, 4).unwrap()
+        };
+        let day4 = days::find(2022, 4).unwrap();
+        let worker = Worker::new(Duration::from_millis(50));
+        assert_eq!(solve(&worker, &slow, 1, "").0, 504);
+        assert_eq!(solve(&worker, day4, 1, "2-4,6-8\n").0, 503);
+        thread::sleep(Duration::from_millis(500));
+        assert_eq!(solve(&worker, day4, 1, "2-4,6-8\n").0, 200);
+    }
+
+    #[test]
+    fn bounded_inputs() {
+        // Deep enough to overflow the stack when adding up the sizes.
+        let (status, json) = handle(
+            &worker(),
+            "POST",
+            "/day/7/part/1",
+            &"$ cd a\n".repeat(200000),
+        );
+        assert_eq!(status, 422, "{json}");
+        assert!(json.contains("directories deep"));
+        // Long enough to keep the solvers busy for hours.
+        assert_eq!(
+            handle(&worker(), "POST", "/day/9/part/1", "R 2000000000\n").0,
+            422
+        );
+        let (dock, _) = include_str!("../../day5/test").split_once("\n\n").unwrap();
+        let moves = format!("{dock}\n\nmove 100000000000 from 1 to 1\n");
+        for part in [1, 2] {
+            let (status, json) = handle(&worker(), "POST", &format!("/day/5/part/{part}"), &moves);
+            assert_eq!(status, 422, "{json}");
+        }
+    }
+
+    #[test]
+    fn solve_only_the_requested_part() {
+        let (_, json) = handle(&worker(), "POST", "/day/4/part/1", "2-4,6-8\n");
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["solve"]["step"], "part1");
+    }
+
+    #[test]
+    fn reject_large_and_idle_requests() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let client = std::thread::spawn(move || {
+            let mut stream = TcpStream::connect(addr).unwrap();
+            let request = format!(
+                "POST /day/4/part/1 HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+                usize::MAX
+            );
+            stream.write_all(request.as_bytes()).unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        });
+        respond(&worker(), listener.accept().unwrap().0).unwrap();
+        assert!(client.join().unwrap().starts_with("HTTP/1.1 413"));
+
+        // A Content-Length that is not a number is not taken for an empty body.
+        let client = std::thread::spawn(move || {
+            let mut stream = TcpStream::connect(addr).unwrap();
+            let request = "POST /day/4/part/1 HTTP/1.1\r\nContent-Length: 12abc\r\n\r\n";
+            stream.write_all(request.as_bytes()).unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        });
+        respond(&worker(), listener.accept().unwrap().0).unwrap();
+        assert!(client.join().unwrap().starts_with("HTTP/1.1 400"));
+
+        // A client sending nothing gets dropped instead of blocking the server.
+        let _idle = TcpStream::connect(addr).unwrap();
+        let start = std::time::Instant::now();
+        assert!(respond(&worker(), listener.accept().unwrap().0).is_err());
+        assert!(start.elapsed() >= READ_TIMEOUT);
+    }
+
+    #[test]
+    fn unknown_routes() {
+        assert_eq!(handle(&worker(), "POST", "/day/4/part/3", "").0, 404);
+        assert_eq!(handle(&worker(), "POST", "/day/25/part/1", "").0, 404);
+        assert_eq!(handle(&worker(), "POST", "/2015/day/4/part/1", "").0, 404);
+        assert_eq!(handle(&worker(), "GET", "/day/4/part/1", "").0, 405);
+        assert_eq!(handle(&worker(), "POST", "/", "").0, 404);
+    }
+}
@@ -0,0 +1,187 @@
+//! The interface every day implements so the runner can parse, solve and measure it.
+
+use crate::alloc::{human_bytes, measure, MemStats};
+use crate::cache;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+pub use plugin::Solution;
+
+/// Timing and memory of one step of a solution (`parse`, `part1` or `part2`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Measurement {
+    pub step: String,
+    pub answer: Result<String, String>,
+    pub time: Duration,
+    pub memory: MemStats,
+}
+
+/// Everything we measured while running one day.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Report {
+    /// Reports recorded before the runner knew of years are from 2022.
+    #[serde(default = "first_year")]
+    pub year: u32,
+    pub day: u32,
+    pub steps: Vec<Measurement>,
+}
+
+fn first_year() -> u32 {
+    crate::days::FIRST_YEAR
+}
+
+impl Report {
+    /// Print one line per step. Answers spanning several lines (day 10 screen) follow the table.
+    pub fn print(&self) {
+        for m in &self.steps {
+            let answer = match &m.answer {
+                Ok(a) if a.contains('\n') => "(see below)".to_string(),
+                Ok(a) => a.clone(),
+                Err(e) => format!("ERROR: {e}"),
+            };
+            println!(
+                "Day {:<3} {:<6} {:<20} {:>10.3?} {:>8} allocs {:>10} {:>10} peak",
+                self.day,
+                m.step,
+                answer,
+                m.time,
+                m.memory.allocations,
+                human_bytes(m.memory.bytes),
+                human_bytes(m.memory.peak)
+            );
+        }
+        for m in &self.steps {
+            if let Ok(a) = &m.answer {
+                if a.contains('\n') {
+                    print!("{a}");
+                }
+            }
+        }
+    }
+}
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration, MemStats) {
+    let start = Instant::now();
+    let (result, memory) = measure(f);
+    (result, start.elapsed(), memory)
+}
+
+/// Parse the input and run both parts, measuring each step.
+pub fn run<S: Solution>(year: u32, day: u32, input: &str) -> Report {
+    run_with(year, day, || S::parse(input), S::part1, S::part2)
+}
+
+/// Parse the input and run only `part`, measuring both steps.
+pub fn run_part<S: Solution>(year: u32, day: u32, input: &str, part: u32) -> Report {
+    let solve: &dyn Fn(&S::Model) -> Result<String, String> = match part {
+        1 => &S::part1,
+        _ => &S::part2,
+    };
+    run_steps(
+        year,
+        day,
+        || S::parse(input),
+        &[(&format!("part{part}"), solve)],
+    )
+}
+
+/// Like `run`, but reuse the model cached in `cache` for this input when there is one. The
+/// answer of the parse step tells when it came from the cache.
+pub fn run_cached<S: Solution>(year: u32, day: u32, input: &str, cache: &Path) -> Report
+where
+    S::Model: Serialize + DeserializeOwned,
+{
+    let mut hit = false;
+    let mut report = run_with(
+        year,
+        day,
+        || match cache::load(cache, year, day, input) {
+            Some(model) => {
+                hit = true;
+                Ok(model)
+            }
+            None => {
+                let model = S::parse(input)?;
+                cache::store(cache, year, day, input, &model);
+                Ok(model)
+            }
+        },
+        S::part1,
+        S::part2,
+    );
+    if hit {
+        report.steps[0].answer = Ok("cached".to_string());
+    }
+    report
+}
+
+/// For `Day::run_cached` of the days whose model is the input itself: caching it would only
+/// store a copy of the input, so they are always run afresh.
+pub fn run_uncached<S: Solution>(year: u32, day: u32, input: &str, _cache: &Path) -> Report {
+    run::<S>(year, day, input)
+}
+
+/// Measure `parse` then, if it succeeds, both parts on the parsed model.
+pub fn run_with<M>(
+    year: u32,
+    day: u32,
+    parse: impl FnOnce() -> Result<M, String>,
+    part1: impl Fn(&M) -> Result<String, String>,
+    part2: impl Fn(&M) -> Result<String, String>,
+) -> Report {
+    run_steps(year, day, parse, &[("part1", &part1), ("part2", &part2)])
+}
+
+/// A part of the puzzle, named after its step.
+type Part<'a, M> = (&'a str, &'a dyn Fn(&M) -> Result<String, String>);
+
+/// Measure `parse` then, if it succeeds, each of the `parts` on the parsed model.
+fn run_steps<M>(
+    year: u32,
+    day: u32,
+    parse: impl FnOnce() -> Result<M, String>,
+    parts: &[Part<M>],
+) -> Report {
+    let (model, time, memory) = timed(parse);
+    let mut steps = vec![Measurement {
+        step: "parse".to_string(),
+        answer: model.as_ref().map(|_| String::new()).map_err(|e| e.clone()),
+        time,
+        memory,
+    }];
+
+    if let Ok(model) = model {
+        for (step, part) in parts {
+            let (answer, time, memory) = timed(|| part(&model));
+            steps.push(Measurement {
+                step: step.to_string(),
+                answer,
+                time,
+                memory,
+            });
+        }
+    }
+    Report { year, day, steps }
+}
+
+#[test]
+fn measure_each_step() {
+    let report = run::<crate::days::Day5>(2022, 5, include_str!("../../day5/test"));
+    let steps = report
+        .steps
+        .iter()
+        .map(|m| m.step.as_str())
+        .collect::<Vec<&str>>();
+    assert_eq!(steps, vec!["parse", "part1", "part2"]);
+    assert_eq!(report.steps[1].answer, Ok("CMZ".to_string()));
+    assert_eq!(report.steps[2].answer, Ok("MCD".to_string()));
+    assert!(report.steps[0].memory.allocations > 0);
+    assert!(report.steps[0].memory.peak <= report.steps[0].memory.bytes);
+
+    let report = run_part::<crate::days::Day5>(2022, 5, include_str!("../../day5/test"), 2);
+    assert_eq!(report.steps.len(), 2);
+    assert_eq!(report.steps[1].step, "part2");
+    assert_eq!(report.steps[1].answer, Ok("MCD".to_string()));
+}
@@ -0,0 +1,152 @@
+//! The advent calendar: which days are solved, verified and healthy.
+
+use crate::batch::{outcomes, read_expected, Outcome};
+use crate::days::{self, Day};
+use std::fs;
+use std::process::{Command, Stdio};
+
+/// Days of an advent calendar.
+pub const CALENDAR_DAYS: u32 = 25;
+
+/// What we know of a registered day.
+#[derive(Debug)]
+pub struct DayStatus {
+    pub day: u32,
+    /// Outcome of each part on the puzzle input, `None` when there is no input.
+    pub parts: Option<[Outcome; 2]>,
+    /// Whether `cargo test` passed for the crates of the day, `None` when not run.
+    pub tests: Option<bool>,
+    pub partial: Option<&'static str>,
+}
+
+impl DayStatus {
+    /// `*` verified, `?` answered but no stored answer, `x` wrong or failing, `.` unknown.
+    pub fn stars(&self) -> String {
+        match &self.parts {
+            Some(parts) => parts
+                .iter()
+                .map(|o| match o {
+                    Outcome::Pass(_) => '*',
+                    Outcome::Unchecked(_) => '?',
+                    Outcome::Fail { .. } | Outcome::Error(_) => 'x',
+                })
+                .collect(),
+            None => "..".to_string(),
+        }
+    }
+
+    /// Both parts are verified and the tests pass.
+    pub fn complete(&self) -> bool {
+        self.stars() == "**" && self.tests != Some(false) && self.partial.is_none()
+    }
+
+    fn remarks(&self) -> Vec<String> {
+        let mut remarks = vec![];
+        if self.parts.is_none() {
+            remarks.push("missing input".to_string());
+        }
+        for (n, part) in self.parts.iter().flatten().enumerate() {
+            match part {
+                Outcome::Fail { answer, expected } if !answer.contains('\n') => {
+                    remarks.push(format!("part{} answered {answer}, not {expected}", n + 1))
+                }
+                Outcome::Fail { .. } => remarks.push(format!("part{} is wrong", n + 1)),
+                Outcome::Error(e) => remarks.push(format!("part{}: {e}", n + 1)),
+                _ => {}
+            }
+        }
+        if self.tests == Some(false) {
+            remarks.push("tests fail".to_string());
+        }
+        if let Some(why) = self.partial {
+            remarks.push(format!("partial: {why}"));
+        }
+        remarks
+    }
+}
+
+fn tests_pass(packages: &[&str]) -> bool {
+    let cargo = std::env::var("CARGO").unwrap_or("cargo".to_string());
+    let mut command = Command::new(cargo);
+    command.args(["test", "--quiet"]);
+    for p in packages {
+        command.args(["-p", p]);
+    }
+    command
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|s| s.success())
+}
+
+/// Solve the day on its input and compare with the stored answers, optionally running the
+/// tests of its crates.
+pub fn check(day: &Day, run_tests: bool) -> DayStatus {
+    let input = day.default_input();
+    let parts = fs::read_to_string(&input).ok().map(|content| {
+        let expected = read_expected(&input).unwrap_or_default();
+        let report = (day.run)(day.year, day.day, &content);
+        outcomes(&report, &expected)
+    });
+    DayStatus {
+        day: day.day,
+        parts,
+        tests: run_tests.then(|| tests_pass(day.packages)),
+        partial: day.partial,
+    }
+}
+
+/// One line per day of the calendar of `year`, with the number of verified stars.
+pub fn print_calendar(year: u32, status: &[DayStatus]) {
+    println!("Advent of Code {year}\n");
+    for day in 1..=CALENDAR_DAYS {
+        match status.iter().find(|s| s.day == day) {
+            Some(s) => println!("Day {day:>2}  {}  {}", s.stars(), s.remarks().join("; ")),
+            None => println!("Day {day:>2}  ..  not solved"),
+        }
+    }
+    let stars = status
+        .iter()
+        .map(|s| s.stars().matches('*').count())
+        .sum::<usize>();
+    let complete = status.iter().filter(|s| s.complete()).count();
+    println!(
+        "\n{stars} of {} stars verified, {complete} of {} solved days complete",
+        2 * CALENDAR_DAYS,
+        days::of_year(year).count()
+    );
+    println!("* verified  ? no stored answer  x wrong or failing  . not available");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stars_and_remarks() {
+        let status = DayStatus {
+            day: 11,
+            parts: Some([
+                Outcome::Error("not solved".to_string()),
+                Outcome::Pass("12848882750".to_string()),
+            ]),
+            tests: Some(true),
+            partial: Some("hardcoded input"),
+        };
+        assert_eq!(status.stars(), "x*");
+        assert!(!status.complete());
+        assert_eq!(
+            status.remarks(),
+            vec!["part1: not solved", "partial: hardcoded input"]
+        );
+
+        let missing = DayStatus {
+            day: 1,
+            parts: None,
+            tests: None,
+            partial: None,
+        };
+        assert_eq!(missing.stars(), "..");
+        assert_eq!(missing.remarks(), vec!["missing input"]);
+    }
+}
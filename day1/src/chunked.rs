@@ -0,0 +1,276 @@
+//! Find the top elves of calorie lists too large to be read in memory.
+//!
+//! The file is cut into ranges ending on blank lines, one per thread. Each thread reads its range
+//! through a fixed buffer, byte by byte, and only keeps its best elves: memory does not grow with
+//! the file. The elves are the ones `parse_diets` would read, separated by blank lines, the last
+//! one with or without a final newline.
+
+use crate::{top_elves, ElfDiet};
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::Path;
+use std::thread;
+
+/// Size of the read buffer of each thread.
+pub const BUFFER_SIZE: usize = 64 * 1024;
+
+/// The best elves of a whole file. Their `items` are not kept, to stay in constant memory.
+#[derive(Debug)]
+pub struct Summary {
+    /// Number of elves in the file.
+    pub elves: usize,
+    /// The elves carrying the most calories, most first.
+    pub top: Vec<ElfDiet>,
+}
+
+/// The elves of a stream, numbered from 0.
+struct Elves<R> {
+    reader: R,
+    buf: Vec<u8>,
+    pos: usize,
+    len: usize,
+    /// Offset in the file of `buf[0]`, to report errors.
+    offset: u64,
+    count: usize,
+}
+
+/// What the reader is in the middle of.
+#[derive(Default)]
+struct Elf {
+    total: i64,
+    items: usize,
+    number: Option<(bool, i64)>,
+    digits: usize,
+    newlines: usize,
+}
+
+impl<R: Read> Elves<R> {
+    fn new(reader: R, offset: u64, buffer_size: usize) -> Self {
+        Elves {
+            reader,
+            buf: vec![0; buffer_size],
+            pos: 0,
+            len: 0,
+            offset,
+            count: 0,
+        }
+    }
+
+    fn next_byte(&mut self) -> Result<Option<(u64, u8)>, String> {
+        if self.pos == self.len {
+            self.offset += self.len as u64;
+            self.len = self.reader.read(&mut self.buf).map_err(|e| e.to_string())?;
+            self.pos = 0;
+            if self.len == 0 {
+                return Ok(None);
+            }
+        }
+        self.pos += 1;
+        Ok(Some((
+            self.offset + self.pos as u64 - 1,
+            self.buf[self.pos - 1],
+        )))
+    }
+
+    fn next_elf(&mut self) -> Result<Option<ElfDiet>, String> {
+        let mut elf = Elf::default();
+        loop {
+            let byte = self.next_byte()?;
+            match byte {
+                Some((_, b'\n')) | None => {
+                    if let Some((negative, n)) = elf.number.take() {
+                        if elf.digits == 0 {
+                            let at = byte.map_or(self.offset, |(at, _)| at);
+                            return Err(format!("Expected a number at offset {at}"));
+                        }
+                        let n = if negative { -n } else { n };
+                        elf.total = elf
+                            .total
+                            .checked_add(n)
+                            .ok_or("Calories add up to more than 2^63")?;
+                        elf.items += 1;
+                        elf.digits = 0;
+                    }
+                    elf.newlines += 1;
+                    if (elf.newlines >= 2 || byte.is_none()) && elf.items > 0 {
+                        self.count += 1;
+                        return Ok(Some(ElfDiet {
+                            total_calories: elf.total,
+                            badge: self.count as i32 - 1,
+                            items: vec![],
+                        }));
+                    }
+                    if byte.is_none() {
+                        return Ok(None);
+                    }
+                }
+                Some((_, b'-')) if elf.number.is_none() => {
+                    elf.number = Some((true, 0));
+                    elf.newlines = 0;
+                }
+                Some((at, c @ b'0'..=b'9')) => {
+                    let (negative, n) = elf.number.unwrap_or((false, 0));
+                    let n = n
+                        .checked_mul(10)
+                        .and_then(|n| n.checked_add((c - b'0') as i64))
+                        .ok_or_else(|| format!("Number too large at offset {at}"))?;
+                    elf.number = Some((negative, n));
+                    elf.digits += 1;
+                    elf.newlines = 0;
+                }
+                Some((at, c)) => {
+                    return Err(format!("Unexpected {:?} at offset {at}", c as char));
+                }
+            }
+        }
+    }
+}
+
+/// Start of the first elf after `offset`, right after a blank line, or the end of the file.
+fn next_boundary(file: &mut File, offset: u64, len: u64) -> io::Result<u64> {
+    if offset == 0 || offset >= len {
+        return Ok(offset.min(len));
+    }
+    file.seek(SeekFrom::Start(offset - 1))?;
+    let mut buf = [0; 4096];
+    let (mut at, mut previous) = (offset - 1, 0);
+    loop {
+        let read = file.read(&mut buf)?;
+        if read == 0 {
+            return Ok(len);
+        }
+        for &b in &buf[..read] {
+            at += 1;
+            if previous == b'\n' && b == b'\n' {
+                return Ok(at);
+            }
+            previous = b;
+        }
+    }
+}
+
+fn top_of_range(
+    path: &Path,
+    (start, end): (u64, u64),
+    n: usize,
+    buffer_size: usize,
+) -> Result<(usize, Vec<ElfDiet>), String> {
+    let mut file = File::open(path).map_err(|e| e.to_string())?;
+    file.seek(SeekFrom::Start(start))
+        .map_err(|e| e.to_string())?;
+    let mut elves = Elves::new(file.take(end - start), start, buffer_size);
+    let mut error = None;
+    let top = top_elves(
+        std::iter::from_fn(|| elves.next_elf().map_err(|e| error = Some(e)).ok()?),
+        n,
+    );
+    match error {
+        Some(e) => Err(e),
+        None => Ok((elves.count, top)),
+    }
+}
+
+fn summarize_with(
+    path: &Path,
+    n: usize,
+    threads: usize,
+    buffer_size: usize,
+) -> Result<Summary, String> {
+    let mut file = File::open(path).map_err(|e| format!("{}: {e}", path.display()))?;
+    let len = file.metadata().map_err(|e| e.to_string())?.len();
+    let mut bounds = vec![0];
+    for i in 1..threads.max(1) as u64 {
+        let bound =
+            next_boundary(&mut file, len * i / threads as u64, len).map_err(|e| e.to_string())?;
+        if bound > *bounds.last().unwrap() {
+            bounds.push(bound);
+        }
+    }
+    bounds.push(len);
+    bounds.dedup();
+
+    let ranges = thread::scope(|s| {
+        bounds
+            .windows(2)
+            .map(|r| s.spawn(move || top_of_range(path, (r[0], r[1]), n, buffer_size)))
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map(|t| t.join().unwrap())
+            .collect::<Result<Vec<_>, String>>()
+    })?;
+
+    // Number the elves of each range after the ones of the ranges before.
+    let mut elves = 0;
+    let mut best = vec![];
+    for (count, top) in ranges {
+        best.extend(top.into_iter().map(|mut diet| {
+            diet.badge += elves as i32;
+            diet
+        }));
+        elves += count;
+    }
+    Ok(Summary {
+        elves,
+        top: top_elves(best, n),
+    })
+}
+
+/// The `n` elves carrying the most calories in the file at `path`, read by `threads` threads.
+pub fn summarize(path: &Path, n: usize, threads: usize) -> Result<Summary, String> {
+    summarize_with(path, n, threads, BUFFER_SIZE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_diets;
+    use std::fs;
+
+    fn with_file(name: &str, content: &str, check: impl Fn(&Path)) {
+        let path = std::env::temp_dir().join(format!("day1-{}-{name}", std::process::id()));
+        fs::write(&path, content).unwrap();
+        check(&path);
+        fs::remove_file(&path).unwrap();
+    }
+
+    fn calories(diets: &[ElfDiet]) -> Vec<(i32, i64)> {
+        diets.iter().map(|d| (d.badge, d.total_calories)).collect()
+    }
+
+    #[test]
+    fn same_elves_as_parse_diets() {
+        let mut input = String::new();
+        for elf in 0..300 {
+            for item in 0..(elf % 7 + 1) {
+                input += &format!("{}\n", (elf * 7919 + item * 104729) % 60000);
+            }
+            input += if elf % 13 == 0 { "\n\n" } else { "\n" };
+        }
+        let expected = top_elves(parse_diets(&input).unwrap(), 5);
+        for content in [input.trim_end(), &input, &format!("\n\n{input}")] {
+            with_file("same", content, |path| {
+                for threads in [1, 2, 3, 8, 64] {
+                    let summary = summarize_with(path, 5, threads, 16).unwrap();
+                    assert_eq!(summary.elves, 300);
+                    assert_eq!(calories(&summary.top), calories(&expected));
+                }
+            });
+        }
+    }
+
+    #[test]
+    fn report_invalid_input() {
+        with_file("invalid", "100\n\n2x0\n", |path| {
+            assert_eq!(
+                summarize_with(path, 3, 2, 4).unwrap_err(),
+                "Unexpected 'x' at offset 6"
+            );
+        });
+        with_file("overflow", "9223372036854775807\n1", |path| {
+            assert!(summarize(path, 3, 1).is_err());
+        });
+        with_file("empty", "", |path| {
+            assert_eq!(summarize(path, 3, 4).unwrap().elves, 0);
+        });
+    }
+}
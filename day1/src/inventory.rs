@@ -0,0 +1,103 @@
+//! Queries on the individual snacks carried by the elves.
+
+use crate::ElfDiet;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// One snack, and the elf carrying it.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+pub struct Item {
+    /// Badge of the elf carrying it.
+    pub badge: i32,
+    /// Calories of the snack.
+    pub calories: i64,
+}
+
+/// The snacks of all the elves, indexed by calories.
+pub struct Inventory<'a> {
+    diets: &'a [ElfDiet],
+    /// Badges of the elves carrying a snack of each amount of calories, in input order.
+    by_calories: BTreeMap<i64, Vec<i32>>,
+}
+
+impl<'a> Inventory<'a> {
+    /// Index the snacks of `diets`.
+    pub fn new(diets: &'a [ElfDiet]) -> Self {
+        let mut by_calories: BTreeMap<i64, Vec<i32>> = BTreeMap::new();
+        for diet in diets {
+            for &calories in &diet.items {
+                let badges = by_calories.entry(calories).or_default();
+                if badges.last() != Some(&diet.badge) {
+                    badges.push(diet.badge);
+                }
+            }
+        }
+        Inventory { diets, by_calories }
+    }
+
+    /// Number of snacks of all the elves.
+    pub fn len(&self) -> usize {
+        self.diets.iter().map(|d| d.items.len()).sum()
+    }
+
+    /// True if no elf carries any snack.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Badges of the elves carrying a snack of exactly `calories`.
+    pub fn carrying(&self, calories: i64) -> &[i32] {
+        self.by_calories
+            .get(&calories)
+            .map_or(&[], |badges| badges.as_slice())
+    }
+
+    /// The snack with the most calories, the first one in the input on a tie.
+    pub fn largest_item(&self) -> Option<Item> {
+        let (&calories, badges) = self.by_calories.last_key_value()?;
+        Some(Item {
+            badge: badges[0],
+            calories,
+        })
+    }
+
+    /// The elves whose heaviest snack has more than `calories`, in input order.
+    pub fn heavier_than(&self, calories: i64) -> Vec<&'a ElfDiet> {
+        self.diets
+            .iter()
+            .filter(|d| d.items.iter().any(|&c| c > calories))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_diets;
+
+    #[test]
+    fn query_the_example() {
+        let diets = parse_diets(include_str!("../test")).unwrap();
+        let inventory = Inventory::new(&diets);
+        assert_eq!(inventory.len(), 10);
+        assert_eq!(inventory.carrying(4000), &[1]);
+        assert!(inventory.carrying(4001).is_empty());
+        assert_eq!(
+            inventory.largest_item(),
+            Some(Item {
+                badge: 4,
+                calories: 10000
+            })
+        );
+        let badges = |diets: Vec<&ElfDiet>| diets.iter().map(|d| d.badge).collect::<Vec<_>>();
+        assert_eq!(badges(inventory.heavier_than(8000)), vec![3, 4]);
+    }
+
+    #[test]
+    fn same_item_twice() {
+        let diets = parse_diets("5\n5\n\n5\n").unwrap();
+        let inventory = Inventory::new(&diets);
+        assert_eq!(inventory.carrying(5), &[0, 1]);
+        assert!(Inventory::new(&[]).largest_item().is_none());
+    }
+}
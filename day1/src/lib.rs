@@ -0,0 +1,112 @@
+//! Day 1: Calorie Counting.
+
+#![warn(missing_docs)]
+
+pub mod chunked;
+pub mod inventory;
+pub mod redistribute;
+pub mod stats;
+
+use parser::{map_opt, number, paragraphs, parse_within, separated_list, tag, ParseError};
+use serde::{Deserialize, Serialize};
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+
+/// The snacks carried by one elf.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ElfDiet {
+    /// Calories of all the snacks.
+    pub total_calories: i64,
+    /// Position of the elf in the input, from 0.
+    pub badge: i32,
+    /// Calories of each snack, in the order of the input.
+    pub items: Vec<i64>,
+}
+
+/// Read one `ElfDiet` per elf. Elves are separated by a blank line.
+pub fn parse_diets(input: &str) -> Result<Vec<ElfDiet>, ParseError> {
+    let calories = map_opt(
+        separated_list(number::<i64>, tag("\n")),
+        |items: Vec<i64>| {
+            let total = items.iter().try_fold(0i64, |sum, c| sum.checked_add(*c))?;
+            Some((total, items))
+        },
+        "calories adding up to less than 2^63",
+    );
+
+    paragraphs(input)
+        .enumerate()
+        .map(|(badge, elf)| {
+            let (total_calories, items) = parse_within(&calories, elf, input)?;
+            Ok(ElfDiet {
+                total_calories,
+                badge: badge as i32,
+                items,
+            })
+        })
+        .collect()
+}
+
+/// Sort the elves, the ones carrying the most calories first.
+pub fn sort_by_calories(diets: &mut [ElfDiet]) {
+    diets.sort_by_key(|d| std::cmp::Reverse(d.total_calories));
+}
+
+/// Ranks elves like `sort_by_calories`: most calories first, then in the order of the input.
+struct ByCalories(ElfDiet);
+
+impl ByCalories {
+    fn key(&self) -> (i64, Reverse<i32>) {
+        (self.0.total_calories, Reverse(self.0.badge))
+    }
+}
+
+impl PartialEq for ByCalories {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for ByCalories {}
+
+impl PartialOrd for ByCalories {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ByCalories {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+/// The `n` elves carrying the most calories, most first, as `sort_by_calories` would rank them.
+///
+/// Only the best `n` elves seen so far are kept, in a min-heap, so the elves can be streamed
+/// and are never all sorted. With fewer than `n` elves, all of them are returned.
+pub fn top_elves(diets: impl IntoIterator<Item = ElfDiet>, n: usize) -> Vec<ElfDiet> {
+    if n == 0 {
+        return vec![];
+    }
+    // `n` comes from the command line: only preallocate what a puzzle input needs.
+    let mut best = BinaryHeap::with_capacity(n.min(1024) + 1);
+    for diet in diets {
+        best.push(Reverse(ByCalories(diet)));
+        if best.len() > n {
+            best.pop();
+        }
+    }
+    best.into_sorted_vec()
+        .into_iter()
+        .map(|Reverse(ByCalories(diet))| diet)
+        .collect()
+}
+
+/// Total calories carried by a group of elves.
+pub fn total_calories(diets: &[ElfDiet]) -> Result<i64, &'static str> {
+    diets
+        .iter()
+        .try_fold(0i64, |sum, elf| sum.checked_add(elf.total_calories))
+        .ok_or("The elves carry 2^63 calories or more")
+}
@@ -1,60 +1,139 @@
-use std::fs::File;
-use std::io::{self, BufRead};
+use day1a::chunked::summarize;
+use day1a::inventory::Inventory;
+use day1a::redistribute::{plan, print_plan};
+use day1a::stats::{print_table, stats};
+use day1a::{parse_diets, top_elves, total_calories, ElfDiet};
+use std::env;
+use std::fs;
 use std::path::Path;
 
-#[derive(Debug)]
-struct ElfDiet {
-    total_calories: i64,
-    badge: i32,
+const USAGE: &str =
+    "Usage: day1a [input] [--top N] [--threads N] [--stats [--json]] [--redistribute [--json]]
+                   [--carrying CALORIES] [--largest-item] [--heavier-than CALORIES]";
+
+/// A question about the individual snacks.
+enum Query {
+    Carrying(i64),
+    LargestItem,
+    HeavierThan(i64),
+}
+
+fn number<T: std::str::FromStr>(arg: Option<String>) -> T {
+    match arg.map(|n| n.parse()) {
+        Some(Ok(n)) => n,
+        _ => panic!("{USAGE}"),
+    }
 }
 
+/// Number of bars of the histogram of `--stats`.
+const HISTOGRAM_BUCKETS: usize = 10;
+
 fn main() {
     // File hosts must exist in current path before this produces output
-    let mut diets = Vec::new();
-
-    let mut current_total = 0;
-    let mut badge = 0;
-
-    if let Ok(lines) = read_lines("./input") {
-        for line in lines {
-            if let Ok(calories) = line {
-                if calories == "" {
-                    // println!("Elf {badge} is all set with {current_total}");
-                    diets.push(ElfDiet {
-                        total_calories: current_total,
-                        badge: badge,
-                    });
-                    current_total = 0;
-                    badge += 1;
-                } else {
-                    current_total += calories.parse::<i64>().unwrap();
-                    // println!("Adding {calories} to current total ({current_total})");
-                }
+    let mut filepath = String::from("./input");
+    let mut n = 3;
+    let (mut show_stats, mut redistribute, mut json) = (false, false, false);
+    let mut query = None;
+    let mut threads = None;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--top" => n = number(args.next()),
+            "--threads" => threads = Some(number(args.next())),
+            "--carrying" => query = Some(Query::Carrying(number(args.next()))),
+            "--largest-item" => query = Some(Query::LargestItem),
+            "--heavier-than" => query = Some(Query::HeavierThan(number(args.next()))),
+            "--stats" => show_stats = true,
+            "--redistribute" => redistribute = true,
+            "--json" => json = true,
+            _ => filepath = arg,
+        }
+    }
+    // Files too large for memory are streamed instead, for the top elves only.
+    if let Some(threads) = threads {
+        match summarize(Path::new(&filepath), n, threads) {
+            Ok(summary) => print_top(&summary.top, n),
+            Err(e) => panic!("Invalid input: {e}"),
+        }
+        return;
+    }
+
+    let input = fs::read_to_string(filepath).unwrap_or_default();
+    let diets = match parse_diets(&input) {
+        Ok(diets) => diets,
+        Err(e) => panic!("Invalid input: {e}"),
+    };
+
+    if show_stats {
+        let Some(stats) = stats(&diets, HISTOGRAM_BUCKETS) else {
+            panic!("No elves in the input");
+        };
+        if json {
+            println!("{}", serde_json::to_string_pretty(&stats).unwrap());
+        } else {
+            print_table(&stats);
+        }
+        return;
+    }
+
+    if redistribute {
+        let plan = match plan(&diets) {
+            Ok(plan) => plan,
+            Err(e) => panic!("{e}"),
+        };
+        if json {
+            println!("{}", serde_json::to_string_pretty(&plan).unwrap());
+        } else {
+            print_plan(&plan);
+        }
+        return;
+    }
+
+    let inventory = Inventory::new(&diets);
+    match query {
+        Some(Query::Carrying(calories)) => {
+            let badges = inventory.carrying(calories);
+            println!(
+                "{} elves carry a snack of {calories}: {badges:?}",
+                badges.len()
+            );
+            return;
+        }
+        Some(Query::LargestItem) => {
+            match inventory.largest_item() {
+                Some(item) => println!("Elf {} carries {} calories", item.badge, item.calories),
+                None => println!("No snacks"),
+            }
+            return;
+        }
+        Some(Query::HeavierThan(calories)) => {
+            let elves = inventory.heavier_than(calories);
+            println!("{} elves carry a snack above {calories}:", elves.len());
+            for diet in elves {
+                println!("  Elf {} with {:?}", diet.badge, diet.items);
             }
+            return;
         }
+        None => {}
     }
 
-    diets.sort_by(|a, b| b.total_calories.cmp(&a.total_calories));
+    print_top(&top_elves(diets, n), n);
+}
+
+fn print_top(top: &[ElfDiet], n: usize) {
+    if top.len() < n {
+        println!("Only {} elves, showing them all.", top.len());
+    }
 
-    println!("Top 3 Elves:");
-    for i in 0..3 {
+    println!("Top {} Elves:", top.len());
+    for diet in top {
         println!(
             "  Elf {} with {} - {:?}",
-            diets[i].badge, diets[i].total_calories, diets[i]
+            diet.badge, diet.total_calories, diet
         );
     }
-    println!(
-        "Top 3 elves have a total of {} calories.",
-        diets[0..3]
-            .iter()
-            .fold(0, |sum, elf| sum + elf.total_calories)
-    );
-}
-
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+    match total_calories(top) {
+        Ok(total) => println!("Top {} elves have a total of {total} calories.", top.len()),
+        Err(e) => println!("Top {} elves: {e}.", top.len()),
+    }
 }
@@ -0,0 +1,289 @@
+//! Share the snacks between the elves so that the most loaded elf carries as little as possible.
+//!
+//! This is multiway number partitioning: NP-hard, so the search is exact only for a few snacks.
+//! Larger inventories use the longest processing time heuristic, which is within 4/3 of the best.
+
+use crate::ElfDiet;
+use serde::Serialize;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+/// Inventories with up to that many snacks are shared exactly.
+pub const EXACT_LIMIT: usize = 20;
+
+/// Give up on the exact search after visiting that many partial plans.
+const SEARCH_BUDGET: usize = 5_000_000;
+
+/// A snack handed from one elf to another.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct Move {
+    /// Calories of the snack.
+    pub calories: i64,
+    /// Badge of the elf giving it.
+    pub from: i32,
+    /// Badge of the elf receiving it.
+    pub to: i32,
+}
+
+/// Snacks to hand over so that the most loaded elf carries as little as possible.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct Plan {
+    /// Calories carried by each elf after the moves, by badge.
+    pub loads: Vec<(i32, i64)>,
+    /// Calories of the most loaded elf before the moves.
+    pub max_before: i64,
+    /// Calories of the most loaded elf after the moves.
+    pub max_after: i64,
+    /// Whether no other plan has a lighter most loaded elf.
+    pub optimal: bool,
+    /// The snacks to hand over.
+    pub moves: Vec<Move>,
+}
+
+/// Snacks, the largest first, with the badge of their owner.
+fn snacks(diets: &[ElfDiet]) -> Vec<(i64, i32)> {
+    let mut snacks = diets
+        .iter()
+        .flat_map(|d| d.items.iter().map(|&c| (c, d.badge)))
+        .collect::<Vec<_>>();
+    snacks.sort_by_key(|&(c, _)| Reverse(c));
+    snacks
+}
+
+/// Each snack goes to the least loaded elf, the largest snacks first.
+fn longest_processing_time(snacks: &[(i64, i32)], elves: usize) -> Vec<usize> {
+    let mut loads = (0..elves)
+        .map(|bin| Reverse((0, bin)))
+        .collect::<BinaryHeap<_>>();
+    snacks
+        .iter()
+        .map(|&(calories, _)| {
+            let Reverse((load, bin)) = loads.pop().unwrap();
+            loads.push(Reverse((load + calories, bin)));
+            bin
+        })
+        .collect()
+}
+
+struct Search<'a> {
+    snacks: &'a [(i64, i32)],
+    lower_bound: i64,
+    best: i64,
+    best_bins: Vec<usize>,
+    bins: Vec<usize>,
+    loads: Vec<i64>,
+    budget: usize,
+}
+
+impl Search<'_> {
+    /// Branch and bound over the bin of each snack. Returns false when out of budget.
+    fn run(&mut self, i: usize) -> bool {
+        if self.budget == 0 {
+            return false;
+        }
+        self.budget -= 1;
+        if i == self.snacks.len() {
+            self.best = *self.loads.iter().max().unwrap();
+            self.best_bins = self.bins.clone();
+            return true;
+        }
+        let calories = self.snacks[i].0;
+        for bin in 0..self.loads.len() {
+            // Bins with the same load are interchangeable.
+            if self.loads[..bin].contains(&self.loads[bin])
+                || self.loads[bin] + calories >= self.best
+            {
+                continue;
+            }
+            self.loads[bin] += calories;
+            self.bins[i] = bin;
+            let finished = self.run(i + 1);
+            self.loads[bin] -= calories;
+            if !finished {
+                return false;
+            }
+            if self.best == self.lower_bound {
+                return true;
+            }
+        }
+        true
+    }
+}
+
+/// Give each bin of snacks to the elf who already owns most of its calories, to move little.
+fn owners(snacks: &[(i64, i32)], bins: &[usize], diets: &[ElfDiet]) -> Vec<i32> {
+    let mut kept: HashMap<(usize, i32), i64> = HashMap::new();
+    for (&(calories, owner), &bin) in snacks.iter().zip(bins) {
+        *kept.entry((bin, owner)).or_default() += calories;
+    }
+    let mut kept = kept.into_iter().collect::<Vec<_>>();
+    kept.sort_by_key(|&((bin, owner), calories)| (Reverse(calories), bin, owner));
+
+    let mut owners = vec![None; diets.len()];
+    let mut taken = HashSet::new();
+    for ((bin, owner), _) in kept {
+        if owners[bin].is_none() && !taken.contains(&owner) {
+            owners[bin] = Some(owner);
+            taken.insert(owner);
+        }
+    }
+    let mut free = diets
+        .iter()
+        .map(|d| d.badge)
+        .filter(|badge| !taken.contains(badge));
+    owners
+        .into_iter()
+        .map(|owner| owner.or_else(|| free.next()).unwrap())
+        .collect()
+}
+
+/// The moves sharing the snacks as evenly as possible between the elves.
+pub fn plan(diets: &[ElfDiet]) -> Result<Plan, &'static str> {
+    if diets.is_empty() {
+        return Err("No elves to share the snacks");
+    }
+    let snacks = snacks(diets);
+    if snacks.iter().any(|&(c, _)| c < 0) {
+        return Err("Snacks with negative calories cannot be shared");
+    }
+
+    // No elf can carry less than the largest snack, nor than an even share.
+    let total = snacks
+        .iter()
+        .try_fold(0i64, |sum, &(c, _)| sum.checked_add(c))
+        .ok_or("The snacks add up to 2^63 calories or more")?;
+    let elves = diets.len() as i64;
+    let even_share = total / elves + i64::from(total % elves != 0);
+    let lower_bound = snacks.first().map_or(0, |&(c, _)| c).max(even_share);
+
+    let heuristic = longest_processing_time(&snacks, diets.len());
+    let mut loads = vec![0; diets.len()];
+    for (&(calories, _), &bin) in snacks.iter().zip(&heuristic) {
+        loads[bin] += calories;
+    }
+    let heuristic_max = *loads.iter().max().unwrap();
+    let (bins, optimal) = if heuristic_max == lower_bound {
+        (heuristic, true)
+    } else if snacks.len() <= EXACT_LIMIT {
+        // Looks for strictly better plans than the heuristic one.
+        let mut search = Search {
+            snacks: &snacks,
+            lower_bound,
+            best: heuristic_max,
+            best_bins: heuristic,
+            bins: vec![0; snacks.len()],
+            loads: vec![0; diets.len()],
+            budget: SEARCH_BUDGET,
+        };
+        let finished = search.run(0);
+        (search.best_bins, finished)
+    } else {
+        (heuristic, false)
+    };
+
+    let owners = owners(&snacks, &bins, diets);
+    let mut loads = diets.iter().map(|d| (d.badge, 0)).collect::<Vec<_>>();
+    let mut moves = vec![];
+    for (&(calories, from), &bin) in snacks.iter().zip(&bins) {
+        let to = owners[bin];
+        if let Some(load) = loads.iter_mut().find(|(badge, _)| *badge == to) {
+            load.1 += calories;
+        }
+        if to != from {
+            moves.push(Move { calories, from, to });
+        }
+    }
+    Ok(Plan {
+        max_after: loads.iter().map(|&(_, load)| load).max().unwrap_or(0),
+        max_before: diets.iter().map(|d| d.total_calories).max().unwrap_or(0),
+        loads,
+        optimal,
+        moves,
+    })
+}
+
+/// Print the moves of the plan and how much the most loaded elf carries before and after.
+pub fn print_plan(plan: &Plan) {
+    for m in &plan.moves {
+        println!(
+            "  Elf {} gives a snack of {} calories to elf {}",
+            m.from, m.calories, m.to
+        );
+    }
+    println!(
+        "{} snack(s) move, the most loaded elf goes from {} to {} calories{}.",
+        plan.moves.len(),
+        plan.max_before,
+        plan.max_after,
+        if plan.optimal {
+            ", the best possible"
+        } else {
+            ""
+        }
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_diets;
+
+    #[test]
+    fn share_the_example() {
+        let diets = parse_diets(include_str!("../test")).unwrap();
+        let plan = plan(&diets).unwrap();
+        assert_eq!(plan.max_before, 24000);
+        assert_eq!(plan.max_after, 11000);
+        assert!(plan.optimal);
+        assert!(plan.loads.iter().all(|&(_, load)| load == 11000));
+        // Elf 4 keeps its 10000 and elf 3 one of its snacks.
+        assert!(plan.moves.iter().all(|m| m.from != 4));
+        assert_eq!(plan.moves.iter().filter(|m| m.from == 3).count(), 2);
+    }
+
+    #[test]
+    fn overflowing_snacks() {
+        let diets = parse_diets("9223372036854775807\n\n9223372036854775807\n").unwrap();
+        assert!(plan(&diets).is_err());
+        let diets = parse_diets("9223372036854775807\n\n0\n").unwrap();
+        assert_eq!(plan(&diets).unwrap().max_after, i64::MAX);
+    }
+
+    #[test]
+    fn exact_beats_the_heuristic() {
+        // Longest processing time puts 3+2+2 against 3+2, 7 / 5, when 3+3 / 2+2+2 is 6 / 6.
+        let diets = parse_diets("3\n3\n2\n2\n2\n\n0\n").unwrap();
+        let snacks = snacks(&diets);
+        let greedy = longest_processing_time(&snacks, 2);
+        let greedy_max = (0..2)
+            .map(|bin| {
+                snacks
+                    .iter()
+                    .zip(&greedy)
+                    .filter(|(_, &b)| b == bin)
+                    .map(|((c, _), _)| c)
+                    .sum::<i64>()
+            })
+            .max();
+        assert_eq!(greedy_max, Some(7));
+
+        let plan = plan(&diets).unwrap();
+        assert_eq!(plan.max_after, 6);
+        assert!(plan.optimal);
+    }
+
+    #[test]
+    fn heuristic_for_many_snacks() {
+        let input = (1..=60)
+            .map(|c| format!("{}\n", c * c % 61))
+            .collect::<String>()
+            + "\n1\n\n2\n";
+        let diets = parse_diets(&input).unwrap();
+        let shared = plan(&diets).unwrap();
+        let total = diets.iter().map(|d| d.total_calories).sum::<i64>();
+        assert_eq!(shared.loads.iter().map(|&(_, l)| l).sum::<i64>(), total);
+        // The heuristic is never worse than the even share plus the largest snack.
+        assert!(shared.max_after <= (total + 2) / 3 + 60);
+        assert!(plan(&[]).is_err());
+    }
+}
@@ -0,0 +1,244 @@
+//! Statistics of the calories carried by all the elves.
+
+use crate::ElfDiet;
+use serde::Serialize;
+
+/// Percentiles of the report, besides the median.
+pub const PERCENTILES: [u32; 6] = [10, 25, 75, 90, 95, 99];
+
+/// Width of the longest bar of the histogram.
+const BAR_WIDTH: usize = 40;
+
+/// Calories carried by the elves.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct Stats {
+    /// Number of elves.
+    pub elves: usize,
+    /// Mean calories per elf.
+    pub mean: f64,
+    /// Median calories per elf.
+    pub median: f64,
+    /// Calories of the least loaded elf.
+    pub min: i64,
+    /// Calories of the most loaded elf.
+    pub max: i64,
+    /// One per value of `PERCENTILES`.
+    pub percentiles: Vec<Percentile>,
+    /// Number of snacks per elf.
+    pub items: ItemStats,
+    /// Number of elves per range of calories.
+    pub histogram: Vec<Bucket>,
+    /// Elves outside of the Tukey fences, 1.5 interquartile ranges beyond the quartiles.
+    pub outliers: Vec<Outlier>,
+}
+
+/// Nearest-rank percentile of the calories carried by the elves.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct Percentile {
+    /// Share of the elves, in percent.
+    pub percent: u32,
+    /// That share of the elves carries these calories or less.
+    pub calories: i64,
+}
+
+/// Number of snacks per elf.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct ItemStats {
+    /// Fewest snacks carried by an elf.
+    pub min: usize,
+    /// Mean number of snacks.
+    pub mean: f64,
+    /// Most snacks carried by an elf.
+    pub max: usize,
+}
+
+/// Elves carrying from `from` up to, but not including, `to` calories.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct Bucket {
+    /// Lowest calories of the bucket.
+    pub from: i64,
+    /// Calories just above the bucket.
+    pub to: i64,
+    /// Number of elves in the bucket.
+    pub elves: usize,
+}
+
+/// An elf carrying unusually many or few calories.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct Outlier {
+    /// Badge of the elf.
+    pub badge: i32,
+    /// Calories it carries.
+    pub total_calories: i64,
+    /// Whether the elf carries a lot more than the others, rather than a lot less.
+    pub high: bool,
+}
+
+/// Nearest-rank percentile of sorted values.
+fn percentile(sorted: &[i64], percent: u32) -> i64 {
+    let rank = (percent as usize * sorted.len()).div_ceil(100);
+    sorted[rank.max(1) - 1]
+}
+
+fn histogram(sorted: &[i64], buckets: usize) -> Vec<Bucket> {
+    let (min, max) = (sorted[0] as i128, sorted[sorted.len() - 1] as i128);
+    let width = ((max - min + 1) as u128).div_ceil(buckets.max(1) as u128) as i128;
+    let mut histogram = (0..buckets.max(1) as i128)
+        .map(|i| Bucket {
+            from: (min + i * width).min(i64::MAX as i128) as i64,
+            to: (min + (i + 1) * width).min(i64::MAX as i128) as i64,
+            elves: 0,
+        })
+        .collect::<Vec<Bucket>>();
+    for &calories in sorted {
+        histogram[((calories as i128 - min) / width) as usize].elves += 1;
+    }
+    histogram
+}
+
+/// Statistics of the elves, with a histogram of `buckets` bars. `None` without elves.
+pub fn stats(diets: &[ElfDiet], buckets: usize) -> Option<Stats> {
+    let mut sorted = diets.iter().map(|d| d.total_calories).collect::<Vec<i64>>();
+    sorted.sort();
+    let n = sorted.len();
+    let (min, max) = (*sorted.first()?, sorted[n - 1]);
+
+    let median = if n % 2 == 0 {
+        (sorted[n / 2 - 1] as f64 + sorted[n / 2] as f64) / 2.0
+    } else {
+        sorted[n / 2] as f64
+    };
+    let (q1, q3) = (
+        percentile(&sorted, 25) as f64,
+        percentile(&sorted, 75) as f64,
+    );
+    let (low, high) = (q1 - 1.5 * (q3 - q1), q3 + 1.5 * (q3 - q1));
+    let outliers = diets
+        .iter()
+        .filter(|d| (d.total_calories as f64) < low || (d.total_calories as f64) > high)
+        .map(|d| Outlier {
+            badge: d.badge,
+            total_calories: d.total_calories,
+            high: d.total_calories as f64 > high,
+        })
+        .collect();
+
+    Some(Stats {
+        elves: n,
+        mean: sorted.iter().map(|&c| c as f64).sum::<f64>() / n as f64,
+        median,
+        min,
+        max,
+        percentiles: PERCENTILES
+            .iter()
+            .map(|&percent| Percentile {
+                percent,
+                calories: percentile(&sorted, percent),
+            })
+            .collect(),
+        items: ItemStats {
+            min: diets.iter().map(|d| d.items.len()).min()?,
+            mean: diets.iter().map(|d| d.items.len()).sum::<usize>() as f64 / n as f64,
+            max: diets.iter().map(|d| d.items.len()).max()?,
+        },
+        histogram: histogram(&sorted, buckets),
+        outliers,
+    })
+}
+
+/// Print the statistics as a table, followed by the histogram and the outliers.
+pub fn print_table(stats: &Stats) {
+    println!("{:<16} {:>14}", "elves", stats.elves);
+    println!("{:<16} {:>14.1}", "mean", stats.mean);
+    println!("{:<16} {:>14.1}", "median", stats.median);
+    println!("{:<16} {:>14}", "min", stats.min);
+    println!("{:<16} {:>14}", "max", stats.max);
+    for p in &stats.percentiles {
+        println!("{:<16} {:>14}", format!("p{}", p.percent), p.calories);
+    }
+    println!(
+        "{:<16} {:>14}",
+        "items per elf",
+        format!(
+            "{} / {:.1} / {}",
+            stats.items.min, stats.items.mean, stats.items.max
+        )
+    );
+
+    println!("\nCalories");
+    let most = stats.histogram.iter().map(|b| b.elves).max().unwrap_or(0);
+    for b in &stats.histogram {
+        let bar = (b.elves * BAR_WIDTH).div_ceil(most.max(1));
+        println!(
+            "{:>10} - {:<10} {:>6} {}",
+            b.from,
+            b.to,
+            b.elves,
+            "#".repeat(bar)
+        );
+    }
+
+    if !stats.outliers.is_empty() {
+        println!("\nOutliers");
+    }
+    for o in &stats.outliers {
+        let side = if o.high { "far above" } else { "far below" };
+        println!(
+            "  Elf {} with {} calories, {side} the others",
+            o.badge, o.total_calories
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_diets;
+
+    #[test]
+    fn example_stats() {
+        let diets = parse_diets(include_str!("../test")).unwrap();
+        let stats = stats(&diets, 4).unwrap();
+        assert_eq!(stats.elves, 5);
+        assert_eq!((stats.min, stats.max), (4000, 24000));
+        assert_eq!((stats.mean, stats.median), (11000.0, 10000.0));
+        assert_eq!(
+            stats.items,
+            ItemStats {
+                min: 1,
+                mean: 2.0,
+                max: 3
+            }
+        );
+        assert_eq!(
+            stats.percentiles[1],
+            Percentile {
+                percent: 25,
+                calories: 6000
+            }
+        );
+        assert_eq!(
+            stats.histogram.iter().map(|b| b.elves).collect::<Vec<_>>(),
+            vec![2, 2, 0, 1]
+        );
+        assert_eq!(stats.histogram[0].from, 4000);
+        assert_eq!(
+            stats.outliers,
+            vec![Outlier {
+                badge: 3,
+                total_calories: 24000,
+                high: true
+            }]
+        );
+    }
+
+    #[test]
+    fn no_elves_no_stats() {
+        assert_eq!(stats(&[], 10), None);
+        let one = parse_diets("7\n").unwrap();
+        let stats = stats(&one, 10).unwrap();
+        assert_eq!(stats.median, 7.0);
+        assert_eq!(stats.histogram.iter().map(|b| b.elves).sum::<usize>(), 1);
+        assert!(stats.outliers.is_empty());
+    }
+}
@@ -0,0 +1,43 @@
+use day1a::{parse_diets, sort_by_calories, top_elves, total_calories};
+
+#[test]
+fn top_three_elves() {
+    let diets = parse_diets(include_str!("../test")).unwrap();
+    let top = top_elves(diets, 3);
+    assert_eq!(
+        top.iter().map(|d| d.badge).collect::<Vec<_>>(),
+        vec![3, 2, 4]
+    );
+    assert_eq!(total_calories(&top), Ok(45000));
+}
+
+#[test]
+fn fewer_elves_than_asked() {
+    let diets = parse_diets("1000\n\n3000\n").unwrap();
+    let top = top_elves(diets.clone(), 5);
+    assert_eq!(top.len(), 2);
+    assert_eq!(top[0].total_calories, 3000);
+    assert!(top_elves(diets, 0).is_empty());
+}
+
+#[test]
+fn same_ranking_as_a_full_sort() {
+    let mut diets = parse_diets("5\n\n3\n\n5\n\n1\n\n3\n\n5\n").unwrap();
+    let top = top_elves(diets.clone(), 4);
+    sort_by_calories(&mut diets);
+    let badges = |d: &[day1a::ElfDiet]| d.iter().map(|d| d.badge).collect::<Vec<_>>();
+    assert_eq!(badges(&top), badges(&diets[..4]));
+}
+
+#[test]
+fn huge_top() {
+    let diets = parse_diets(include_str!("../test")).unwrap();
+    assert_eq!(top_elves(diets.clone(), usize::MAX).len(), 5);
+    assert_eq!(top_elves(diets, usize::MAX / 2).len(), 5);
+}
+
+#[test]
+fn overflowing_total() {
+    let diets = parse_diets("9223372036854775807\n\n9223372036854775807\n").unwrap();
+    assert!(total_calories(&top_elves(diets, 3)).is_err());
+}
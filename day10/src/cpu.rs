@@ -0,0 +1,111 @@
+//! The CPU and its CRT.
+
+use crate::Instruction;
+
+/// Pixels in a row of the CRT.
+pub const CRT_WIDTH: usize = 40;
+/// Rows of the CRT.
+pub const CRT_LINES: usize = 6;
+
+/// Error of a program taking the register or the signal strength out of an `i32`.
+pub const OVERFLOW: &str = "The program overflows the CPU";
+
+/// The handheld device CPU, wired to its CRT screen.
+#[allow(clippy::upper_case_acronyms)]
+pub struct CPU<'p> {
+    reg_x: i32,
+    cycle: usize,
+    pgm: &'p mut dyn Iterator<Item = Instruction>,
+    signal_strength: i32,
+    crt: [char; CRT_WIDTH * CRT_LINES],
+}
+impl<'a> CPU<'a> {
+    /// A CPU that will execute `pgm`.
+    pub fn new(pgm: &'a mut dyn Iterator<Item = Instruction>) -> Self {
+        CPU {
+            pgm,
+            reg_x: 1,
+            cycle: 0,
+            signal_strength: 0,
+            crt: [' '; 240],
+        }
+    }
+
+    /// Run the program to the end and return the sum of the signal strengths. The CPU stops at
+    /// the first overflow.
+    pub fn run(&mut self) -> Result<i32, &'static str> {
+        while let Some(i) = self.pgm.next() {
+            match i {
+                Instruction::Noop => self.i_noop()?,
+                Instruction::AddX(x) => self.i_add(x)?,
+            }
+        }
+        Ok(self.signal_strength)
+    }
+
+    /// Print what is on the CRT.
+    pub fn display(&self) {
+        print!("{}", self.screen());
+    }
+
+    /// What is on the CRT, one line per row.
+    pub fn screen(&self) -> String {
+        self.crt
+            .chunks(CRT_WIDTH)
+            .map(|line| line.iter().collect::<String>() + "\n")
+            .collect()
+    }
+
+    fn i_noop(&mut self) -> Result<(), &'static str> {
+        self.cycle()
+    }
+
+    fn i_add(&mut self, x: i32) -> Result<(), &'static str> {
+        self.cycle()?;
+        self.cycle()?;
+        self.reg_x = self.reg_x.checked_add(x).ok_or(OVERFLOW)?;
+        Ok(())
+    }
+
+    fn cycle(&mut self) -> Result<(), &'static str> {
+        // CRT Drawing: the sprite covers the register and the points on each side of it.
+        let cur_x = self.cycle % CRT_WIDTH;
+        let cur_y = (self.cycle / CRT_WIDTH) % CRT_LINES;
+        if (cur_x as i32).abs_diff(self.reg_x) <= 1 {
+            self.crt[cur_x + CRT_WIDTH * cur_y] = '#';
+        } else {
+            self.crt[cur_x + CRT_WIDTH * cur_y] = '.';
+        }
+
+        self.cycle += 1;
+        if self.cycle == 20 || (self.cycle > 20 && (self.cycle - 20).is_multiple_of(40)) {
+            let strength = i32::try_from(self.cycle)
+                .ok()
+                .and_then(|cycle| cycle.checked_mul(self.reg_x));
+            self.signal_strength = strength
+                .and_then(|s| self.signal_strength.checked_add(s))
+                .ok_or(OVERFLOW)?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_part_1() {
+    let data = include_str!("../input");
+    let mut pgm = crate::parse_program(data).unwrap().into_iter();
+    let mut cpu = CPU::new(&mut pgm);
+    assert_eq!(cpu.run(), Ok(14920));
+}
+
+#[test]
+fn overflow() {
+    let mut pgm = crate::parse_program("addx 2147483647\naddx 1\n")
+        .unwrap()
+        .into_iter();
+    assert_eq!(CPU::new(&mut pgm).run(), Err(OVERFLOW));
+    let mut pgm = crate::parse_program(&"addx 100000000\n".repeat(11))
+        .unwrap()
+        .into_iter();
+    assert_eq!(CPU::new(&mut pgm).run(), Err(OVERFLOW));
+}
@@ -0,0 +1,47 @@
+//! Day 10: Cathode-Ray Tube.
+
+#![warn(missing_docs)]
+
+pub mod cpu;
+
+use parser::{map, number, or, parse_all, parse_lines, preceded, tag, value, PResult, ParseError};
+use serde::{Deserialize, Serialize};
+
+/// One instruction of the CPU.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Instruction {
+    /// `noop`: wait one cycle.
+    Noop,
+    /// `addx V`: add `V` to the register, in two cycles.
+    AddX(i32),
+}
+/// `noop` or `addx -5`
+pub fn instruction(i: &str) -> PResult<'_, Instruction> {
+    or(
+        value(Instruction::Noop, tag("noop")),
+        map(preceded(tag("addx "), number), Instruction::AddX),
+    )(i)
+}
+
+impl TryFrom<&str> for Instruction {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        parse_all(instruction, value)
+    }
+}
+
+/// Read the program, one instruction per line.
+pub fn parse_program(data: &str) -> Result<Vec<Instruction>, ParseError> {
+    parse_lines(instruction, data)
+}
+
+#[test]
+fn locate_errors() {
+    let e = parse_program("noop\naddx 3\naddx x\n").unwrap_err();
+    assert_eq!((e.line, e.column), (3, 6));
+    assert_eq!(
+        parse_program("noop\naddx -5\n"),
+        Ok(vec![Instruction::Noop, Instruction::AddX(-5)])
+    );
+}
@@ -0,0 +1,155 @@
+//! Day 11: Monkey in the Middle.
+
+#![warn(missing_docs)]
+
+use serde::{Deserialize, Serialize};
+
+/// Worry levels kept as the remainders of the divisions the monkeys test.
+pub mod worry;
+
+/// Worry levels grow too fast to be stored, we only keep what matters for the tests.
+pub type WorryLevel = worry::Worry;
+
+/// What a monkey does to your worry level when it inspects an item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Operation {
+    /// `old + n`
+    Add(u32),
+    /// `old * n`
+    Mul(u32),
+    /// `old * old`
+    Square,
+}
+
+impl Operation {
+    /// Update the worry level of an item.
+    pub fn apply(&self, x: &mut WorryLevel) {
+        match self {
+            Operation::Add(y) => x.add(*y),
+            Operation::Mul(y) => x.mul(*y),
+            Operation::Square => x.square(),
+        }
+    }
+}
+
+/// A monkey, the items it holds and how it decides where to throw them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Monkey {
+    /// Worry levels of the items it holds.
+    pub items: Vec<WorryLevel>,
+    /// How inspecting an item changes its worry level.
+    pub operation: Operation,
+    /// The divisor deciding where an item goes.
+    pub test_divide_by: u32,
+    /// Monkey receiving the items whose worry level is divisible.
+    pub yes_throw_to: usize,
+    /// Monkey receiving the other items.
+    pub no_throw_to: usize,
+    /// Items inspected so far.
+    pub inspections: usize,
+}
+
+impl Monkey {
+    /// A monkey holding `initial_items`, throwing to `yes_throw_to` when the worry level is
+    /// divisible by `test_divide_by` and to `no_throw_to` otherwise.
+    pub fn new(
+        initial_items: &[u32],
+        operation: Operation,
+        test_divide_by: u32,
+        yes_throw_to: usize,
+        no_throw_to: usize,
+    ) -> Self {
+        Self {
+            items: initial_items
+                .iter()
+                .map(WorryLevel::from)
+                .collect::<Vec<WorryLevel>>(),
+            operation,
+            test_divide_by,
+            yes_throw_to,
+            no_throw_to,
+            inspections: 0,
+        }
+    }
+
+    /// Inspect all items in the monkey hand and return a list of items to add to other monkeys.
+    pub fn inspect_all_items(&self) -> Vec<(usize, WorryLevel)> {
+        let mut transfers = vec![];
+        // for i in 0..self.items.len() {
+        self.items.iter().for_each(|it| {
+            // println!("  Monkey inspects an item with level of {}", *it);
+
+            let mut new_value = it.clone();
+            self.operation.apply(&mut new_value);
+            // println!("    changed to {}", new_value);
+            // new_value /= 3;
+            // println!("    divided to {}", new_value);
+
+            let next_monkey = if new_value.dividable_by(self.test_divide_by) {
+                self.yes_throw_to
+            } else {
+                self.no_throw_to
+            };
+            // println!(
+            //     "    {} - level {} thrown to {}",
+            //     new_value.dividable_by(self.test_divide_by),
+            //     new_value,
+            //     next_monkey
+            // );
+            transfers.push((next_monkey, new_value));
+        });
+        transfers
+    }
+}
+
+/// The monkeys from my puzzle input.
+pub fn puzzle_monkeys() -> Vec<Monkey> {
+    vec![
+        Monkey::new(&[64], Operation::Mul(7), 13, 1, 3),
+        Monkey::new(&[60, 84, 84, 65], Operation::Add(7), 19, 2, 7),
+        Monkey::new(&[52, 67, 74, 88, 51, 61], Operation::Mul(3), 5, 5, 7),
+        Monkey::new(&[67, 72], Operation::Add(3), 2, 1, 2),
+        Monkey::new(
+            &[80, 79, 58, 77, 68, 74, 98, 64],
+            Operation::Square,
+            17,
+            6,
+            0,
+        ),
+        Monkey::new(&[62, 53, 61, 89, 86], Operation::Add(8), 11, 4, 6),
+        Monkey::new(&[86, 89, 82], Operation::Add(2), 7, 3, 0),
+        Monkey::new(&[92, 81, 70, 96, 69, 84, 83], Operation::Add(4), 3, 4, 5),
+    ]
+}
+
+/// Every monkey, in order, inspects and throws all its items.
+pub fn run_round(monkeys: &mut [Monkey]) {
+    for i in 0..monkeys.len() {
+        // println!("Monkey {i}");
+
+        let m = &monkeys[i];
+        let transfers = m.inspect_all_items();
+        monkeys[i].inspections += transfers.len();
+        for (to, val) in transfers {
+            monkeys[to].items.push(val);
+        }
+        monkeys[i].items.clear();
+    }
+}
+
+/// Print how many items each monkey inspected.
+pub fn print_monkeys(monkeys: &[Monkey]) {
+    for (i, m) in monkeys.iter().enumerate() {
+        println!("Monkey {} ({} inspections) ", i, m.inspections)
+    }
+}
+
+/// Product of the number of inspections of the two most active monkeys.
+pub fn monkey_business(monkeys: &[Monkey]) -> usize {
+    let mut inspections = monkeys
+        .iter()
+        .map(|m| m.inspections)
+        .collect::<Vec<usize>>();
+    inspections.sort_unstable_by(|a, b| b.cmp(a));
+    inspections.iter().take(2).product()
+}
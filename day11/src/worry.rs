@@ -33,15 +33,20 @@ How about, we just store the result of mod x
 
 */
 
+use serde::{Deserialize, Serialize};
+
 const INTERESTING_PRIMES: [u32; 8] = [13, 19, 5, 2, 17, 11, 7, 3];
 
-#[derive(Clone, Debug)]
+/// A worry level, as its remainders by the primes the monkeys divide by.
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Worry {
     mod_of: [u32; INTERESTING_PRIMES.len()],
     // v: u128,
 }
 
+#[allow(clippy::needless_range_loop)]
 impl Worry {
+    /// The worry level `x`.
     pub fn from(x: &u32) -> Self {
         let mut mods = [0; INTERESTING_PRIMES.len()];
 
@@ -76,6 +81,7 @@ impl Worry {
         // self.self_check("add");
     }
 
+    /// Multiply the worry level by itself.
     pub fn square(&mut self) {
         // self.v *= self.v;
         for i in 0..INTERESTING_PRIMES.len() {
@@ -86,6 +92,7 @@ impl Worry {
     }
 
     /// A number is dividable by y if the rest of the euclidian division is 0
+    #[allow(clippy::assertions_on_constants)]
     pub fn dividable_by(&self, y: u32) -> bool {
         // let result = self.v % y as u128 == 0;
 
@@ -125,6 +132,7 @@ impl Worry {
 }
 
 use std::fmt;
+#[allow(clippy::needless_range_loop)]
 impl fmt::Display for Worry {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         // Write strictly the first element into the supplied output
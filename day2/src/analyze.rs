@@ -0,0 +1,245 @@
+//! Score a strategy guide under every way of reading its second column.
+//!
+//! `X`, `Y` and `Z` can stand for the three shapes in any order, or for the three results in any
+//! order: twelve mappings, one of which the elf had in mind.
+
+use crate::{score_round, Decoder, GuideRound, RoundResult, RPS};
+use std::fmt;
+
+/// Above that share of won rounds, a guide would look suspicious: the elf said so.
+pub const SUSPICIOUS_WIN_RATE: f64 = 2.0 / 3.0;
+
+/// What `X`, `Y` and `Z` stand for, in that order.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mapping {
+    /// The shapes to play.
+    Shapes([RPS; 3]),
+    /// How the rounds must end.
+    Results([RoundResult; 3]),
+}
+
+impl Mapping {
+    /// The shape played against `opponent` when the second column says `letter`, which must be
+    /// `X`, `Y` or `Z`.
+    pub fn read(&self, opponent: RPS, letter: char) -> Result<RPS, String> {
+        let i = match letter {
+            'X' => 0,
+            'Y' => 1,
+            'Z' => 2,
+            _ => return Err(format!("Invalid letter {letter:?}, expected X, Y or Z")),
+        };
+        Ok(match self {
+            Mapping::Shapes(shapes) => shapes[i],
+            Mapping::Results(results) => opponent.response_for(results[i]),
+        })
+    }
+}
+
+impl Decoder for Mapping {
+    /// Parsed guides only hold `X`, `Y` and `Z`: any other letter is a bug of the caller.
+    fn decode(&self, opponent: RPS, letter: char) -> RPS {
+        self.read(opponent, letter)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+impl fmt::Display for Mapping {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let meanings = match self {
+            Mapping::Shapes(shapes) => shapes.map(|s| format!("{s:?}")),
+            Mapping::Results(results) => results.map(|r| format!("{r:?}")),
+        };
+        write!(f, "X={} Y={} Z={}", meanings[0], meanings[1], meanings[2])
+    }
+}
+
+/// The 3! orders of three values.
+fn permutations<T: Copy>([a, b, c]: [T; 3]) -> [[T; 3]; 6] {
+    [
+        [a, b, c],
+        [a, c, b],
+        [b, a, c],
+        [b, c, a],
+        [c, a, b],
+        [c, b, a],
+    ]
+}
+
+/// All the mappings, the puzzle's two readings first.
+pub fn mappings() -> Vec<Mapping> {
+    let shapes = permutations([RPS::Rock, RPS::Paper, RPS::Scissor]).map(Mapping::Shapes);
+    let results = permutations([RoundResult::Lose, RoundResult::Draw, RoundResult::Win])
+        .map(Mapping::Results);
+    shapes.into_iter().chain(results).collect()
+}
+
+/// Outcome of following the guide with one mapping.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MappingScore {
+    /// How the second column was read.
+    pub mapping: Mapping,
+    /// Total score over the guide.
+    pub score: i32,
+    /// Rounds won.
+    pub wins: usize,
+    /// Rounds ending in a draw.
+    pub draws: usize,
+    /// Rounds lost.
+    pub losses: usize,
+}
+
+impl MappingScore {
+    /// Share of the rounds won, 0 for an empty guide.
+    pub fn win_rate(&self) -> f64 {
+        self.wins as f64 / (self.wins + self.draws + self.losses).max(1) as f64
+    }
+}
+
+/// The guide scored under every mapping.
+#[derive(Debug)]
+pub struct Analysis {
+    /// Best score first.
+    pub scores: Vec<MappingScore>,
+    /// Average score when all the mappings are equally likely.
+    pub expected: f64,
+    /// The best scoring mapping that does not win suspiciously often, or the best one.
+    pub intended: MappingScore,
+}
+
+impl Analysis {
+    /// The highest scoring mapping.
+    pub fn best(&self) -> &MappingScore {
+        &self.scores[0]
+    }
+
+    /// The lowest scoring mapping.
+    pub fn worst(&self) -> &MappingScore {
+        &self.scores[self.scores.len() - 1]
+    }
+}
+
+/// Score the guide under every mapping, in one pass over the guide.
+pub fn analyze(guide: &[GuideRound]) -> Analysis {
+    let mut scores = mappings()
+        .into_iter()
+        .map(|mapping| MappingScore {
+            mapping,
+            score: 0,
+            wins: 0,
+            draws: 0,
+            losses: 0,
+        })
+        .collect::<Vec<_>>();
+    for round in guide {
+        for s in scores.iter_mut() {
+            let played = round.decode(&s.mapping);
+            s.score += score_round(&played);
+            if played.you.beats(&played.opponent) {
+                s.wins += 1;
+            } else if played.you == played.opponent {
+                s.draws += 1;
+            } else {
+                s.losses += 1;
+            }
+        }
+    }
+    // Stable: on a tie, the puzzle's readings come first.
+    scores.sort_by_key(|s| std::cmp::Reverse(s.score));
+
+    let expected = scores.iter().map(|s| s.score as f64).sum::<f64>() / scores.len() as f64;
+    let intended = scores
+        .iter()
+        .find(|s| s.win_rate() <= SUSPICIOUS_WIN_RATE)
+        .unwrap_or(&scores[0])
+        .clone();
+    Analysis {
+        scores,
+        expected,
+        intended,
+    }
+}
+
+/// Print the score of every mapping, and which one the elf most likely meant.
+pub fn print_analysis(analysis: &Analysis) {
+    println!(
+        "{:<34} {:>7} {:>6} {:>6} {:>6}",
+        "mapping", "score", "wins", "draws", "losses"
+    );
+    for s in &analysis.scores {
+        println!(
+            "{:<34} {:>7} {:>6} {:>6} {:>6}",
+            s.mapping.to_string(),
+            s.score,
+            s.wins,
+            s.draws,
+            s.losses
+        );
+    }
+    println!();
+    println!(
+        "Best:     {} ({})",
+        analysis.best().score,
+        analysis.best().mapping
+    );
+    println!(
+        "Worst:    {} ({})",
+        analysis.worst().score,
+        analysis.worst().mapping
+    );
+    println!("Expected: {:.1}", analysis.expected);
+    println!(
+        "Intended: {} ({}), winning {:.0}% of the rounds",
+        analysis.intended.score,
+        analysis.intended.mapping,
+        100.0 * analysis.intended.win_rate()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_guide, total_scores, AsResult, AsShape};
+
+    #[test]
+    fn puzzle_readings_are_mappings() {
+        let guide = parse_guide(include_str!("../test")).unwrap();
+        let analysis = analyze(&guide);
+        assert_eq!(analysis.scores.len(), 12);
+        let score_of = |m: Mapping| {
+            analysis
+                .scores
+                .iter()
+                .find(|s| s.mapping == m)
+                .unwrap()
+                .score
+        };
+        let [shapes, results] = total_scores(&guide, [&AsShape, &AsResult]);
+        assert_eq!(score_of(mappings()[0]), shapes);
+        assert_eq!(score_of(mappings()[6]), results);
+        assert!(analysis.worst().score <= results && results <= analysis.best().score);
+    }
+
+    #[test]
+    fn best_worst_and_intended() {
+        // Against rocks, reading X as Paper wins 3 rounds out of 4: too good to be true.
+        let guide = parse_guide("A X\nA X\nA X\nA Y").unwrap();
+        let analysis = analyze(&guide);
+        assert_eq!(analysis.best().score, 3 * 8 + 4);
+        assert_eq!(analysis.best().win_rate(), 0.75);
+        assert_eq!(analysis.worst().score, 3 * 3 + 4);
+        // Drawing three times and winning once.
+        assert_eq!(analysis.intended.score, 3 * 4 + 8);
+        let mean = analysis.scores.iter().map(|s| s.score).sum::<i32>() as f64 / 12.0;
+        assert_eq!(analysis.expected, mean);
+    }
+
+    #[test]
+    fn reject_unknown_letters() {
+        let mapping = mappings()[0];
+        assert_eq!(mapping.read(RPS::Rock, 'Z'), Ok(RPS::Scissor));
+        assert_eq!(
+            mapping.read(RPS::Rock, 'W'),
+            Err("Invalid letter 'W', expected X, Y or Z".to_string())
+        );
+    }
+}
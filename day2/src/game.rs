@@ -0,0 +1,273 @@
+//! Cyclic hand games with any odd number of shapes, described by a rules file.
+//!
+//! The shapes are listed in a cycle where each one beats the `(n - 1) / 2` shapes before it, the
+//! first one coming after the last: every shape beats as many shapes as it loses to. A rules file
+//! has one directive per line, `#` starting a comment:
+//!
+//! - `shape <name> <score> <opponent letter> <your letter>`, in the order of the cycle
+//! - `outcomes <letters>`, the second column read as how the round ends, from the biggest loss to
+//!   the biggest win: the middle letter is a draw, the next one the shape just after the
+//!   opponent's, and so on
+//! - `points <lose> <draw> <win>`, 0 3 6 when missing
+
+use parser::{
+    eof, map, map_opt, number, or, pair, parse_lines, preceded, rest, separated_list,
+    separated_pair, spaces1, tag, terminated, value, word, PResult,
+};
+use std::collections::HashSet;
+
+/// Rules of the puzzle's Rock Paper Scissors.
+pub const CLASSIC: &str = include_str!("../rules/rock-paper-scissors");
+
+/// A shape of the game, from a `shape` directive.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Shape {
+    /// Name of the shape, eg: `rock`.
+    pub name: String,
+    /// Points for playing the shape.
+    pub score: i32,
+    /// Letter of the shape in the first column of the guide.
+    pub opponent: char,
+    /// Letter of the shape in the second column, when it is read as the shape to play.
+    pub you: char,
+}
+
+/// A game read from a rules file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Game {
+    /// The shapes, in the order of the cycle.
+    pub shapes: Vec<Shape>,
+    /// Letters of the second column when it is read as the outcome, biggest loss first.
+    pub outcomes: Vec<char>,
+    /// Points for losing, drawing and winning a round.
+    pub points: [i32; 3],
+}
+
+/// How to read the second column of the guide.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Column {
+    /// The shape to play.
+    Shape,
+    /// How the round must end.
+    Outcome,
+}
+
+/// One round of a guide: the opponent's shape and the letter of the second column.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Round {
+    /// Index of the opponent's shape in `Game::shapes`.
+    pub opponent: usize,
+    /// Letter of the second column.
+    pub letter: char,
+}
+
+#[derive(Clone)]
+enum Directive {
+    Shape(Shape),
+    Outcomes(Vec<char>),
+    Points(Vec<i32>),
+    Blank,
+}
+
+fn letter(i: &str) -> PResult<'_, char> {
+    map_opt(
+        word,
+        |w| {
+            let mut chars = w.chars();
+            chars.next().filter(|_| chars.next().is_none())
+        },
+        "a single letter",
+    )(i)
+}
+
+/// `shape Rock 1 A X`
+fn shape(i: &str) -> PResult<'_, Shape> {
+    map(
+        preceded(
+            tag("shape "),
+            pair(
+                pair(terminated(word, spaces1), terminated(number, spaces1)),
+                separated_pair(letter, spaces1, letter),
+            ),
+        ),
+        |((name, score), (opponent, you))| Shape {
+            name: name.to_string(),
+            score,
+            opponent,
+            you,
+        },
+    )(i)
+}
+
+fn directive(i: &str) -> PResult<'_, Directive> {
+    or(
+        or(
+            map(shape, Directive::Shape),
+            map(
+                preceded(tag("outcomes "), separated_list(letter, spaces1)),
+                Directive::Outcomes,
+            ),
+        ),
+        or(
+            map(
+                preceded(tag("points "), separated_list(number, spaces1)),
+                Directive::Points,
+            ),
+            or(
+                value(Directive::Blank, preceded(tag("#"), rest)),
+                value(Directive::Blank, eof),
+            ),
+        ),
+    )(i)
+}
+
+impl Game {
+    /// Read the rules of a game, checking that it is fair and that letters are not ambiguous.
+    pub fn parse(rules: &str) -> Result<Game, String> {
+        let mut game = Game {
+            shapes: vec![],
+            outcomes: vec![],
+            points: [0, 3, 6],
+        };
+        for d in parse_lines(directive, rules).map_err(|e| e.to_string())? {
+            match d {
+                Directive::Shape(s) => game.shapes.push(s),
+                Directive::Outcomes(letters) => game.outcomes = letters,
+                Directive::Points(p) => {
+                    game.points = p
+                        .try_into()
+                        .map_err(|_| "points needs 3 values: lose, draw and win")?
+                }
+                Directive::Blank => {}
+            }
+        }
+
+        let n = game.shapes.len();
+        if n < 3 || n.is_multiple_of(2) {
+            return Err(format!(
+                "A fair game needs an odd number of shapes, not {n}"
+            ));
+        }
+        if !game.outcomes.is_empty() && game.outcomes.len() != n {
+            return Err(format!("{n} shapes need {n} outcomes"));
+        }
+        let distinct = |letters: Vec<char>| letters.iter().collect::<HashSet<_>>().len() == n;
+        if !distinct(game.shapes.iter().map(|s| s.opponent).collect())
+            || !distinct(game.shapes.iter().map(|s| s.you).collect())
+            || !(game.outcomes.is_empty() || distinct(game.outcomes.clone()))
+        {
+            return Err("The letters of a column must all differ".to_string());
+        }
+        Ok(game)
+    }
+
+    /// The puzzle's Rock Paper Scissors.
+    pub fn classic() -> Game {
+        Game::parse(CLASSIC).unwrap()
+    }
+
+    /// True if shape `a` beats shape `b`, given by their position in the cycle.
+    pub fn beats(&self, a: usize, b: usize) -> bool {
+        let n = self.shapes.len();
+        let distance = (a + n - b) % n;
+        (1..=n / 2).contains(&distance)
+    }
+
+    /// Score of playing `you` against `opponent`: the shape plus the outcome.
+    pub fn score(&self, opponent: usize, you: usize) -> i32 {
+        let outcome = if self.beats(you, opponent) {
+            self.points[2]
+        } else if you == opponent {
+            self.points[1]
+        } else {
+            self.points[0]
+        };
+        self.shapes[you].score + outcome
+    }
+
+    /// Read a guide: one round per line, the opponent's letter then the second column.
+    pub fn read_guide(&self, guide: &str) -> Result<Vec<Round>, String> {
+        let opponent = |i| {
+            map_opt(
+                letter,
+                |c| self.shapes.iter().position(|s| s.opponent == c),
+                "the letter of a shape",
+            )(i)
+        };
+        let round = map(separated_pair(opponent, tag(" "), letter), |(o, l)| Round {
+            opponent: o,
+            letter: l,
+        });
+        parse_lines(round, guide).map_err(|e| e.to_string())
+    }
+
+    /// The shape you play in `round` when reading its second column as `column`.
+    pub fn play(&self, round: &Round, column: Column) -> Result<usize, String> {
+        let n = self.shapes.len();
+        let position = match column {
+            Column::Shape => self.shapes.iter().position(|s| s.you == round.letter),
+            Column::Outcome => self.outcomes.iter().position(|&c| c == round.letter).map(
+                // The middle outcome is a draw, the ones after it are shapes after the opponent's.
+                |o| (round.opponent + n + o - n / 2) % n,
+            ),
+        };
+        position.ok_or_else(|| format!("{} is not a letter of the {column:?} column", round.letter))
+    }
+
+    /// Total score when following every round of the guide.
+    pub fn total_score(&self, rounds: &[Round], column: Column) -> Result<i32, String> {
+        rounds
+            .iter()
+            .map(|r| Ok(self.score(r.opponent, self.play(r, column)?)))
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classic_game() {
+        let game = Game::classic();
+        assert!(game.beats(1, 0) && game.beats(0, 2) && !game.beats(0, 1));
+        let rounds = game.read_guide(include_str!("../test")).unwrap();
+        assert_eq!(game.total_score(&rounds, Column::Shape), Ok(15));
+        assert_eq!(game.total_score(&rounds, Column::Outcome), Ok(12));
+    }
+
+    #[test]
+    fn lizard_spock() {
+        let game = Game::parse(include_str!("../rules/rock-paper-scissors-lizard-spock")).unwrap();
+        let names = |i: usize| game.shapes[i].name.as_str();
+        let beaten = |a| {
+            (0..5)
+                .filter(|&b| game.beats(a, b))
+                .map(names)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(beaten(0), vec!["Lizard", "Scissors"]);
+        assert_eq!(beaten(1), vec!["Rock", "Scissors"]);
+        assert_eq!(beaten(3), vec!["Spock", "Paper"]);
+
+        // Spock loses to Lizard, Paper beats Rock, Spock against Spock is a draw.
+        let rounds = game.read_guide("D W\nA X\nB W").unwrap();
+        assert_eq!(game.total_score(&rounds, Column::Shape), Ok(2 + 9 + 5));
+        // Lose to Lizard by the closest shape, draw with Rock, lose to Spock.
+        let plays = rounds
+            .iter()
+            .map(|r| names(game.play(r, Column::Outcome).unwrap()))
+            .collect::<Vec<_>>();
+        assert_eq!(plays, vec!["Paper", "Rock", "Rock"]);
+    }
+
+    #[test]
+    fn unfair_rules() {
+        let four = "shape A 1 A W\nshape B 2 B X\nshape C 3 C Y\nshape D 4 D Z\n";
+        assert!(Game::parse(four).unwrap_err().contains("odd number"));
+        let twice = "shape A 1 A X\nshape B 2 A Y\nshape C 3 C Z\n";
+        assert!(Game::parse(twice).is_err());
+        assert!(Game::parse("shape A one A X").is_err());
+        assert!(Game::classic().read_guide("D X").is_err());
+    }
+}
@@ -0,0 +1,220 @@
+//! Day 2: Rock Paper Scissors.
+//!
+//! The meaning of the second column of the guide is left to a `Decoder`: the shape to play for
+//! part one, how the round must end for part two.
+
+#![warn(missing_docs)]
+
+pub mod analyze;
+pub mod game;
+pub mod opponent;
+pub mod players;
+pub mod tournament;
+
+use parser::{map, one_of, parse_all, parse_lines, separated_pair, tag, PResult, ParseError};
+use serde::{Deserialize, Serialize};
+
+/// A hand shape.
+#[allow(clippy::upper_case_acronyms)]
+#[derive(PartialEq, Debug, Copy, Clone, Serialize, Deserialize)]
+pub enum RPS {
+    /// `A` or `X`.
+    Rock,
+    /// `B` or `Y`.
+    Paper,
+    /// `C` or `Z`.
+    Scissor,
+}
+
+/// How a round ends.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum RoundResult {
+    /// `Z`.
+    Win,
+    /// `X`.
+    Lose,
+    /// `Y`.
+    Draw,
+}
+
+/// One line of the guide, before deciding what the second column means.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct GuideRound {
+    /// The shape of the first column.
+    pub opponent: RPS,
+    /// `X`, `Y` or `Z`.
+    pub letter: char,
+}
+
+/// One round of the strategy guide, with the shape we play.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RPSRound {
+    /// The shape the opponent plays.
+    pub opponent: RPS,
+    /// The shape we play.
+    pub you: RPS,
+}
+
+/// The shapes, in the order of `RPS::index`.
+pub const SHAPES: [RPS; 3] = [RPS::Rock, RPS::Paper, RPS::Scissor];
+
+impl RPS {
+    /// Position in `SHAPES`.
+    pub fn index(&self) -> usize {
+        *self as usize
+    }
+
+    /// `A`/`X` is Rock, `B`/`Y` is Paper and `C`/`Z` is Scissor.
+    pub fn from(s: &str) -> RPS {
+        match s {
+            "A" | "X" => RPS::Rock,
+            "B" | "Y" => RPS::Paper,
+            "C" | "Z" => RPS::Scissor,
+            &_ => panic!("Invalid input {s}"),
+        }
+    }
+
+    /// True if `self` wins against `other`.
+    pub fn beats(&self, other: &RPS) -> bool {
+        // Rock defeats Scissors, Scissors defeats Paper, and Paper defeats Rock
+        match self {
+            RPS::Rock => other == &RPS::Scissor,
+            RPS::Scissor => other == &RPS::Paper,
+            RPS::Paper => other == &RPS::Rock,
+        }
+    }
+
+    /// The shape to play against `self` to get the result `r`.
+    pub fn response_for(&self, r: RoundResult) -> RPS {
+        match r {
+            RoundResult::Draw => *self,
+            // We want to win ...
+            RoundResult::Win => match self {
+                RPS::Rock => RPS::Paper,
+                RPS::Paper => RPS::Scissor,
+                RPS::Scissor => RPS::Rock,
+            },
+            // We want to lose ...
+            RoundResult::Lose => match self {
+                RPS::Rock => RPS::Scissor,
+                RPS::Paper => RPS::Rock,
+                RPS::Scissor => RPS::Paper,
+            },
+        }
+    }
+}
+
+impl RoundResult {
+    /// `X` means lose, `Y` draw and `Z` win.
+    pub fn from(s: &str) -> RoundResult {
+        match s {
+            "X" => RoundResult::Lose,
+            "Y" => RoundResult::Draw,
+            "Z" => RoundResult::Win,
+            &_ => panic!("Invalid input {s}"),
+        }
+    }
+}
+
+/// What the second column of the guide means: the shape we play against `opponent`.
+pub trait Decoder {
+    /// The shape we play against `opponent` when the second column says `letter`.
+    fn decode(&self, opponent: RPS, letter: char) -> RPS;
+}
+
+/// Part one: the second column is the shape to play.
+pub struct AsShape;
+
+impl Decoder for AsShape {
+    fn decode(&self, _: RPS, letter: char) -> RPS {
+        RPS::from(letter.encode_utf8(&mut [0; 4]))
+    }
+}
+
+/// Part two: the second column is how the round needs to end.
+pub struct AsResult;
+
+impl Decoder for AsResult {
+    fn decode(&self, opponent: RPS, letter: char) -> RPS {
+        opponent.response_for(RoundResult::from(letter.encode_utf8(&mut [0; 4])))
+    }
+}
+
+impl GuideRound {
+    /// The round played when reading the second column with `decoder`.
+    pub fn decode(&self, decoder: &dyn Decoder) -> RPSRound {
+        RPSRound {
+            opponent: self.opponent,
+            you: decoder.decode(self.opponent, self.letter),
+        }
+    }
+}
+
+impl RPSRound {
+    /// Parse one line of the guide, eg: `A Y`, reading the second column as a shape.
+    pub fn read(s: &str) -> Result<RPSRound, ParseError> {
+        Ok(parse_all(guide_round, s)?.decode(&AsShape))
+    }
+
+    /// Score of the round: the shape we played plus the outcome of the round.
+    pub fn score(&self) -> i32 {
+        score_round(self)
+    }
+}
+
+/// One line of the guide: `A Y`
+pub fn guide_round(i: &str) -> PResult<'_, GuideRound> {
+    map(
+        separated_pair(one_of("ABC"), tag(" "), one_of("XYZ")),
+        |(o, letter)| GuideRound {
+            opponent: RPS::from(o.encode_utf8(&mut [0; 4])),
+            letter,
+        },
+    )(i)
+}
+
+/// Read every line of the strategy guide.
+pub fn parse_guide(input: &str) -> Result<Vec<GuideRound>, ParseError> {
+    parse_lines(guide_round, input)
+}
+
+/// Read every round of the strategy guide, the second column being the shape to play.
+pub fn parse_rounds(input: &str) -> Result<Vec<RPSRound>, ParseError> {
+    Ok(parse_guide(input)?
+        .iter()
+        .map(|r| r.decode(&AsShape))
+        .collect())
+}
+
+/// Score of a round: the shape you played plus the outcome of the round.
+pub fn score_round(r: &RPSRound) -> i32 {
+    let mut score = match r.you {
+        RPS::Rock => 1,
+        RPS::Paper => 2,
+        RPS::Scissor => 3,
+    };
+
+    if r.you.beats(&r.opponent) {
+        score += 6
+    } else if r.you == r.opponent {
+        score += 3
+    }
+
+    score
+}
+
+/// Total score when following the whole guide.
+pub fn total_score(rounds: &[RPSRound]) -> i32 {
+    rounds.iter().map(score_round).sum()
+}
+
+/// Total score of the guide read with each of the `decoders`, in one pass over the guide.
+pub fn total_scores<const N: usize>(guide: &[GuideRound], decoders: [&dyn Decoder; N]) -> [i32; N] {
+    let mut totals = [0; N];
+    for round in guide {
+        for (total, decoder) in totals.iter_mut().zip(decoders) {
+            *total += score_round(&round.decode(decoder));
+        }
+    }
+    totals
+}
@@ -1,92 +1,71 @@
-use std::fs::File;
-use std::io::{self, BufRead, BufReader};
-use std::path::Path;
+use day2::analyze::{analyze, print_analysis};
+use day2::game::{Column, Game};
+use day2::opponent::{print_report, simulate};
+use day2::tournament::{entrants, print_leaderboard, tournament};
+use day2::{AsResult, AsShape};
+use std::env;
+use std::fs;
 
-#[derive(PartialEq, Debug)]
-enum RPS {
-    Rock,
-    Paper,
-    Scissor,
-}
-
-#[derive(Debug)]
-struct RPSRound {
-    opponent: RPS,
-    you: RPS,
-}
+const USAGE: &str = "Usage: day2 [input] [--rules FILE | --analyze | --simulate [--seed N] | --tournament [--rounds N] [--seed N]]";
 
-impl RPS {
-    fn from(s: &str) -> RPS {
-        match s {
-            "A" | "X" => RPS::Rock,
-            "B" | "Y" => RPS::Paper,
-            "C" | "Z" => RPS::Scissor,
-            &_ => panic!("Invalid input {s}"),
+fn main() {
+    let mut filepath = String::from("./input");
+    let mut rules = None;
+    let mut analysis = false;
+    let mut simulation = false;
+    let mut contest = false;
+    let mut rounds = None;
+    let mut seed = 2022;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--rules" => rules = Some(args.next().expect(USAGE)),
+            "--analyze" => analysis = true,
+            "--simulate" => simulation = true,
+            "--tournament" => contest = true,
+            "--rounds" => rounds = Some(args.next().and_then(|n| n.parse().ok()).expect(USAGE)),
+            "--seed" => seed = args.next().and_then(|n| n.parse().ok()).expect(USAGE),
+            _ => filepath = arg,
         }
     }
+    let input = fs::read_to_string(filepath).expect("Cannot read input");
 
-    fn beats(&self, other: &RPS) -> bool {
-        // Rock defeats Scissors, Scissors defeats Paper, and Paper defeats Rock
-        match self {
-            RPS::Rock => other == &RPS::Scissor,
-            RPS::Scissor => other == &RPS::Paper,
-            RPS::Paper => other == &RPS::Rock,
+    // Any other game than the puzzle's is read from its rules file.
+    if let Some(rules) = rules {
+        let rules = fs::read_to_string(rules).expect("Cannot read rules");
+        let game = Game::parse(&rules).unwrap_or_else(|e| panic!("Invalid rules: {e}"));
+        let rounds = game
+            .read_guide(&input)
+            .unwrap_or_else(|e| panic!("Cannot parse input: {e}"));
+        println!("Loaded {} rounds.", rounds.len());
+        for column in [Column::Shape, Column::Outcome] {
+            match game.total_score(&rounds, column) {
+                Ok(score) => println!("Reading the second column as the {column:?}: {score}."),
+                Err(e) => println!("Cannot read the second column as the {column:?}: {e}"),
+            }
         }
+        return;
     }
-}
 
-impl RPSRound {
-    fn read(s: &str) -> RPSRound {
-        let letters = s.split(" ").collect::<Vec<&str>>();
-        RPSRound {
-            opponent: RPS::from(letters[0]),
-            you: RPS::from(letters[1]),
-        }
-    }
-}
+    let guide = day2::parse_guide(&input).unwrap_or_else(|e| panic!("Cannot parse input: {e}"));
+    println!("Loaded {} rounds.", guide.len());
 
-fn process_input(lines: io::Lines<BufReader<File>>) -> Vec<RPSRound> {
-    let mut rounds = Vec::new();
-    for line in lines {
-        if let Ok(l) = line {
-            println!("Reading {l}");
-            rounds.push(RPSRound::read(&l));
-        }
+    if analysis {
+        print_analysis(&analyze(&guide));
+        return;
     }
-    println!("Loaded {} rounds.", rounds.len());
-    rounds
-}
-
-fn score_round(r: &RPSRound) -> i32 {
-    let mut score = match r.you {
-        RPS::Rock => 1,
-        RPS::Paper => 2,
-        RPS::Scissor => 3,
-    };
-
-    if r.you.beats(&r.opponent) {
-        score += 6
-    } else if r.you == r.opponent {
-        score += 3
+    if simulation {
+        print_report(&simulate(&guide, seed));
+        return;
+    }
+    if contest {
+        // As many rounds as the guide has, by default.
+        let rounds = rounds.unwrap_or(guide.len());
+        print_leaderboard(&tournament(&entrants(&guide, seed), rounds));
+        return;
     }
 
-    println!("Round {:?} => {score}", r);
-
-    score
-}
-
-fn main() {
-    let score = match read_lines("./input") {
-        Ok(lines) => process_input(lines).iter().map(score_round).sum(),
-        _ => 0,
-    };
-    println!("Your score with this strategy would be {score}.");
-}
-
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+    let [shapes, results] = day2::total_scores(&guide, [&AsShape, &AsResult]);
+    println!("Your score with this strategy would be {shapes}.");
+    println!("Following the Elf's instructions for the second column, it would be {results}.");
 }
@@ -0,0 +1,188 @@
+//! A Markov model of the opponent column of the guide, and how players fare against it.
+
+use crate::players::{play_against, FrequencyCounter, MarkovPredictor, Player, Random, Rng, Tally};
+use crate::{total_scores, AsResult, GuideRound, RPS, SHAPES};
+
+/// First-order Markov chain over the opponent's shapes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Markov {
+    /// Probability of each shape in the first round, indexed by `RPS::index`.
+    pub initial: [f64; 3],
+    /// Probability of each shape after each shape, indexed by `RPS::index`.
+    pub transitions: [[f64; 3]; 3],
+}
+
+/// Counts to probabilities, with add-one smoothing so that no shape is impossible.
+fn normalize(counts: [u32; 3]) -> [f64; 3] {
+    let total = counts.iter().sum::<u32>() as f64 + 3.0;
+    counts.map(|n| (n as f64 + 1.0) / total)
+}
+
+fn pick(rng: &mut Rng, probabilities: &[f64; 3]) -> RPS {
+    let mut x = rng.unit();
+    for (i, p) in probabilities.iter().enumerate() {
+        if x < *p {
+            return SHAPES[i];
+        }
+        x -= p;
+    }
+    SHAPES[2]
+}
+
+impl Markov {
+    /// Fit the chain to the opponent column of the guide.
+    pub fn fit(guide: &[GuideRound]) -> Markov {
+        let mut initial = [0; 3];
+        let mut transitions = [[0; 3]; 3];
+        if let Some(first) = guide.first() {
+            initial[first.opponent.index()] += 1;
+        }
+        for pair in guide.windows(2) {
+            transitions[pair[0].opponent.index()][pair[1].opponent.index()] += 1;
+        }
+        Markov {
+            initial: normalize(initial),
+            transitions: transitions.map(normalize),
+        }
+    }
+
+    /// `rounds` shapes drawn from the chain.
+    pub fn sample(&self, rng: &mut Rng, rounds: usize) -> Vec<RPS> {
+        let mut shapes = Vec::with_capacity(rounds);
+        let mut last: Option<RPS> = None;
+        for _ in 0..rounds {
+            let next = match last {
+                None => pick(rng, &self.initial),
+                Some(last) => pick(rng, &self.transitions[last.index()]),
+            };
+            shapes.push(next);
+            last = Some(next);
+        }
+        shapes
+    }
+}
+
+/// How a player did against the guide's opponent, and against one drawn from its model.
+#[derive(Debug)]
+pub struct Simulation {
+    /// Name of the player.
+    pub player: String,
+    /// Against the opponent of the guide.
+    pub recorded: Tally,
+    /// Against an opponent drawn from the model.
+    pub modeled: Tally,
+}
+
+/// The model of the opponent and how every player fared against it.
+#[derive(Debug)]
+pub struct Report {
+    /// The opponent, as fitted on the guide.
+    pub model: Markov,
+    /// Score when following the guide, its second column being the result to get.
+    pub guide: i32,
+    /// One per player.
+    pub simulations: Vec<Simulation>,
+}
+
+/// Mixed into the seed of the random player, so its shapes do not follow those of the modeled
+/// opponent, drawn from the same seed.
+const PLAYER_SEED: u64 = 0xa076_1d64_78bd_642f;
+
+/// The players of the simulation, the random one seeded with `seed`.
+pub fn players(seed: u64) -> Vec<Box<dyn Player>> {
+    vec![
+        Box::new(FrequencyCounter::default()),
+        Box::new(MarkovPredictor::default()),
+        Box::new(Random::new(seed)),
+    ]
+}
+
+/// Fit the opponent of the guide and let every player face it, as recorded and as modeled.
+pub fn simulate(guide: &[GuideRound], seed: u64) -> Report {
+    let model = Markov::fit(guide);
+    let recorded = guide.iter().map(|r| r.opponent).collect::<Vec<RPS>>();
+    let modeled = model.sample(&mut Rng::new(seed), guide.len());
+    let [guide_score] = total_scores(guide, [&AsResult]);
+
+    let simulations = players(seed ^ PLAYER_SEED)
+        .into_iter()
+        .zip(players(seed ^ PLAYER_SEED))
+        .map(|(mut a, mut b)| Simulation {
+            player: a.name(),
+            recorded: play_against(a.as_mut(), &recorded),
+            modeled: play_against(b.as_mut(), &modeled),
+        })
+        .collect();
+    Report {
+        model,
+        guide: guide_score,
+        simulations,
+    }
+}
+
+/// Print the model of the opponent and the score of every player next to the guide's.
+pub fn print_report(report: &Report) {
+    println!("Opponent model, probability of the next shape:");
+    println!(
+        "{:>10} {:>8} {:>8} {:>8}",
+        "after", "Rock", "Paper", "Scissor"
+    );
+    println!(
+        "{:>10} {:>8.3} {:>8.3} {:>8.3}",
+        "start", report.model.initial[0], report.model.initial[1], report.model.initial[2]
+    );
+    for (shape, p) in SHAPES.iter().zip(report.model.transitions) {
+        println!(
+            "{:>10} {:>8.3} {:>8.3} {:>8.3}",
+            format!("{shape:?}"),
+            p[0],
+            p[1],
+            p[2]
+        );
+    }
+    println!();
+    println!(
+        "{:<20} {:>10} {:>8} {:>10} {:>8}",
+        "player", "recorded", "vs guide", "modeled", "vs guide"
+    );
+    for s in &report.simulations {
+        println!(
+            "{:<20} {:>10} {:>+8} {:>10} {:>+8}",
+            s.player,
+            s.recorded.score,
+            s.recorded.score as i64 - i64::from(report.guide),
+            s.modeled.score,
+            s.modeled.score as i64 - i64::from(report.guide)
+        );
+    }
+    println!("\nFollowing the guide scores {}.", report.guide);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_guide;
+
+    #[test]
+    fn fit_a_predictable_opponent() {
+        let guide = parse_guide(&"A Y\nB X\nC Z\n".repeat(20)).unwrap();
+        let model = Markov::fit(&guide);
+        // Rock is always followed by Paper: 20 times, smoothed over 23.
+        assert_eq!(model.transitions[0], [1.0 / 23.0, 21.0 / 23.0, 1.0 / 23.0]);
+        assert!(model.initial[0] > model.initial[1]);
+
+        let sample = model.sample(&mut Rng::new(1), 60);
+        let follows = sample
+            .windows(2)
+            .filter(|w| w[1] == w[0].response_for(crate::RoundResult::Win))
+            .count();
+        assert!(follows > 45, "{follows}");
+
+        let report = simulate(&guide, 1);
+        assert_eq!(report.guide, total_scores(&guide, [&AsResult])[0]);
+        let markov = &report.simulations[1];
+        assert_eq!(markov.player, "markov predictor");
+        assert!(markov.recorded.wins >= 55, "{markov:?}");
+        assert!(markov.recorded.score > report.simulations[0].recorded.score);
+    }
+}
@@ -0,0 +1,236 @@
+//! Players choosing their shape round after round, from what they saw of their opponent.
+
+use crate::{score_round, AsResult, GuideRound, RPSRound, RoundResult, RPS, SHAPES};
+
+/// So that random players can be replayed from a seed.
+pub use rng::Rng;
+
+/// Something playing Rock Paper Scissors.
+pub trait Player {
+    /// Name on the leaderboards.
+    fn name(&self) -> String;
+    /// The shape for the next round.
+    fn play(&mut self) -> RPS;
+    /// What was played in the round, to learn from it.
+    fn observe(&mut self, _you: RPS, _opponent: RPS) {}
+}
+
+/// Plays the shape beating the opponent's most frequent shape so far.
+#[derive(Default)]
+pub struct FrequencyCounter {
+    counts: [u32; 3],
+}
+
+impl Player for FrequencyCounter {
+    fn name(&self) -> String {
+        "frequency counter".to_string()
+    }
+
+    fn play(&mut self) -> RPS {
+        let favourite = (0..3).max_by_key(|&i| (self.counts[i], 2 - i)).unwrap();
+        SHAPES[favourite].response_for(RoundResult::Win)
+    }
+
+    fn observe(&mut self, _: RPS, opponent: RPS) {
+        self.counts[opponent.index()] += 1;
+    }
+}
+
+/// Learns which shape the opponent plays after each shape, and beats the likeliest one.
+#[derive(Default)]
+pub struct MarkovPredictor {
+    transitions: [[u32; 3]; 3],
+    last: Option<RPS>,
+    fallback: FrequencyCounter,
+}
+
+impl Player for MarkovPredictor {
+    fn name(&self) -> String {
+        "markov predictor".to_string()
+    }
+
+    fn play(&mut self) -> RPS {
+        match self.last {
+            Some(last) if self.transitions[last.index()].iter().any(|&n| n > 0) => {
+                let next = &self.transitions[last.index()];
+                let likeliest = (0..3).max_by_key(|&i| (next[i], 2 - i)).unwrap();
+                SHAPES[likeliest].response_for(RoundResult::Win)
+            }
+            _ => self.fallback.play(),
+        }
+    }
+
+    fn observe(&mut self, you: RPS, opponent: RPS) {
+        if let Some(last) = self.last {
+            self.transitions[last.index()][opponent.index()] += 1;
+        }
+        self.last = Some(opponent);
+        self.fallback.observe(you, opponent);
+    }
+}
+
+/// Plays uniformly at random.
+pub struct Random {
+    rng: Rng,
+}
+
+impl Random {
+    /// A player drawing its shapes from `seed`.
+    pub fn new(seed: u64) -> Random {
+        Random {
+            rng: Rng::new(seed),
+        }
+    }
+}
+
+impl Player for Random {
+    fn name(&self) -> String {
+        "random".to_string()
+    }
+
+    fn play(&mut self) -> RPS {
+        SHAPES[self.rng.below(3) as usize]
+    }
+}
+
+/// Plays the shapes of the guide, its second column being the result to get, starting over at
+/// its end.
+pub struct GuideFollower {
+    shapes: Vec<RPS>,
+    round: usize,
+}
+
+impl GuideFollower {
+    /// A player following `guide`.
+    pub fn new(guide: &[GuideRound]) -> GuideFollower {
+        GuideFollower {
+            shapes: guide.iter().map(|r| r.decode(&AsResult).you).collect(),
+            round: 0,
+        }
+    }
+}
+
+impl Player for GuideFollower {
+    fn name(&self) -> String {
+        "guide follower".to_string()
+    }
+
+    fn play(&mut self) -> RPS {
+        let shape = self.shapes.get(self.round % self.shapes.len().max(1));
+        self.round += 1;
+        // An empty guide says nothing: fall back on Rock.
+        shape.copied().unwrap_or(RPS::Rock)
+    }
+}
+
+/// Plays the same shape every round.
+pub struct Always(pub RPS);
+
+impl Player for Always {
+    fn name(&self) -> String {
+        format!("always {:?}", self.0).to_lowercase()
+    }
+
+    fn play(&mut self) -> RPS {
+        self.0
+    }
+}
+
+/// Plays Rock, Paper, Scissor, Rock, ...
+#[derive(Default)]
+pub struct Cycle {
+    round: usize,
+}
+
+impl Player for Cycle {
+    fn name(&self) -> String {
+        "cycle".to_string()
+    }
+
+    fn play(&mut self) -> RPS {
+        self.round += 1;
+        SHAPES[(self.round - 1) % 3]
+    }
+}
+
+/// Score and results of a series of rounds.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Tally {
+    /// Total score, large enough for any number of rounds.
+    pub score: u64,
+    /// Rounds won.
+    pub wins: usize,
+    /// Rounds ending in a draw.
+    pub draws: usize,
+    /// Rounds lost.
+    pub losses: usize,
+}
+
+impl Tally {
+    /// Count one more round.
+    pub fn add(&mut self, round: &RPSRound) {
+        self.score += score_round(round) as u64;
+        if round.you.beats(&round.opponent) {
+            self.wins += 1;
+        } else if round.you == round.opponent {
+            self.draws += 1;
+        } else {
+            self.losses += 1;
+        }
+    }
+}
+
+/// Let `player` play against the `opponent` shapes, one round each.
+pub fn play_against(player: &mut dyn Player, opponent: &[RPS]) -> Tally {
+    let mut tally = Tally::default();
+    for &theirs in opponent {
+        let you = player.play();
+        tally.add(&RPSRound {
+            opponent: theirs,
+            you,
+        });
+        player.observe(you, theirs);
+    }
+    tally
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adaptive_players_learn() {
+        let rocks = vec![RPS::Rock; 10];
+        let tally = play_against(&mut FrequencyCounter::default(), &rocks);
+        // Having seen nothing yet, it expects Rock as well.
+        assert_eq!(tally.wins, 10);
+
+        let cycle = SHAPES.repeat(10);
+        let tally = play_against(&mut MarkovPredictor::default(), &cycle);
+        assert!(tally.wins >= 27, "{tally:?}");
+        let tally = play_against(&mut FrequencyCounter::default(), &cycle);
+        assert!(tally.wins <= 12, "{tally:?}");
+    }
+
+    #[test]
+    fn simple_players() {
+        let guide = crate::parse_guide(include_str!("../test")).unwrap();
+        let mut follower = GuideFollower::new(&guide);
+        let played = (0..4).map(|_| follower.play()).collect::<Vec<_>>();
+        assert_eq!(played, [RPS::Rock, RPS::Rock, RPS::Rock, RPS::Rock]);
+        assert_eq!(GuideFollower::new(&[]).play(), RPS::Rock);
+
+        let mut cycle = Cycle::default();
+        let played = (0..4).map(|_| cycle.play()).collect::<Vec<_>>();
+        assert_eq!(played, [RPS::Rock, RPS::Paper, RPS::Scissor, RPS::Rock]);
+        assert_eq!(Always(RPS::Rock).name(), "always rock");
+    }
+
+    #[test]
+    fn random_is_seeded() {
+        let rocks = vec![RPS::Rock; 30];
+        let once = play_against(&mut Random::new(7), &rocks);
+        assert_eq!(play_against(&mut Random::new(7), &rocks), once);
+        assert_eq!(once.wins + once.draws + once.losses, 30);
+    }
+}
@@ -0,0 +1,135 @@
+//! A round-robin tournament: every engine plays every other one over the same number of rounds.
+
+use crate::players::{Always, Cycle, GuideFollower, MarkovPredictor, Player, Random, Tally};
+use crate::{GuideRound, RPSRound, RPS};
+
+/// Makes a fresh engine for each of its matches.
+pub type Entrant<'a> = Box<dyn Fn() -> Box<dyn Player> + 'a>;
+
+/// The engines of the tournament, the random one seeded with `seed`.
+pub fn entrants(guide: &[GuideRound], seed: u64) -> Vec<Entrant<'_>> {
+    vec![
+        Box::new(move || Box::new(GuideFollower::new(guide))),
+        Box::new(|| Box::new(Always(RPS::Rock))),
+        Box::new(|| Box::new(Cycle::default())),
+        Box::new(move || Box::new(Random::new(seed))),
+        Box::new(|| Box::new(MarkovPredictor::default())),
+    ]
+}
+
+/// Play `rounds` rounds between `a` and `b`, each of them seeing the other's shapes.
+pub fn play_match(a: &mut dyn Player, b: &mut dyn Player, rounds: usize) -> (Tally, Tally) {
+    let (mut tally_a, mut tally_b) = (Tally::default(), Tally::default());
+    for _ in 0..rounds {
+        let (shape_a, shape_b) = (a.play(), b.play());
+        tally_a.add(&RPSRound {
+            opponent: shape_b,
+            you: shape_a,
+        });
+        tally_b.add(&RPSRound {
+            opponent: shape_a,
+            you: shape_b,
+        });
+        a.observe(shape_a, shape_b);
+        b.observe(shape_b, shape_a);
+    }
+    (tally_a, tally_b)
+}
+
+/// An engine and its rounds over the whole tournament.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Standing {
+    /// Name of the engine.
+    pub player: String,
+    /// Its rounds, summed over all its matches.
+    pub tally: Tally,
+}
+
+/// Play every pair of entrants once and rank them, highest total score first.
+pub fn tournament(entrants: &[Entrant], rounds: usize) -> Vec<Standing> {
+    let mut standings = entrants
+        .iter()
+        .map(|make| Standing {
+            player: make().name(),
+            tally: Tally::default(),
+        })
+        .collect::<Vec<_>>();
+    for i in 0..entrants.len() {
+        for j in i + 1..entrants.len() {
+            let (a, b) = play_match(entrants[i]().as_mut(), entrants[j]().as_mut(), rounds);
+            for (standing, tally) in [(i, a), (j, b)] {
+                let total = &mut standings[standing].tally;
+                total.score += tally.score;
+                total.wins += tally.wins;
+                total.draws += tally.draws;
+                total.losses += tally.losses;
+            }
+        }
+    }
+    // Stable: on a tie, the order of the entrants is kept.
+    standings.sort_by_key(|s| {
+        (
+            std::cmp::Reverse(s.tally.score),
+            std::cmp::Reverse(s.tally.wins),
+        )
+    });
+    standings
+}
+
+/// Print the standings, one row per engine.
+pub fn print_leaderboard(standings: &[Standing]) {
+    println!(
+        "{:>4} {:<20} {:>8} {:>7} {:>7} {:>7}",
+        "rank", "player", "score", "wins", "draws", "losses"
+    );
+    for (rank, s) in standings.iter().enumerate() {
+        println!(
+            "{:>4} {:<20} {:>8} {:>7} {:>7} {:>7}",
+            rank + 1,
+            s.player,
+            s.tally.score,
+            s.tally.wins,
+            s.tally.draws,
+            s.tally.losses
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_guide;
+
+    #[test]
+    fn matches_are_symmetric() {
+        let (rock, cycle) = play_match(&mut Always(RPS::Rock), &mut Cycle::default(), 6);
+        // Rock draws with Rock, loses to Paper and beats Scissor, twice.
+        assert_eq!((rock.wins, rock.draws, rock.losses), (2, 2, 2));
+        assert_eq!((cycle.wins, cycle.draws, cycle.losses), (2, 2, 2));
+        assert_eq!(rock.score, 6 + 2 * (6 + 3));
+        assert_eq!(cycle.score, 2 * (1 + 2 + 3) + 2 * (3 + 6));
+    }
+
+    #[test]
+    fn leaderboard() {
+        let guide = parse_guide(include_str!("../test")).unwrap();
+        let entrants = entrants(&guide, 2022);
+        let standings = tournament(&entrants, 300);
+        assert_eq!(standings.len(), 5);
+        assert!(standings
+            .windows(2)
+            .all(|w| w[0].tally.score >= w[1].tally.score));
+        for s in &standings {
+            // Four matches each.
+            assert_eq!(s.tally.wins + s.tally.draws + s.tally.losses, 4 * 300);
+        }
+        // Every round won by someone was lost by someone else.
+        let wins = standings.iter().map(|s| s.tally.wins).sum::<usize>();
+        assert_eq!(
+            wins,
+            standings.iter().map(|s| s.tally.losses).sum::<usize>()
+        );
+        // The adaptive engine exploits the ones always playing Rock.
+        assert_eq!(standings[0].player, "markov predictor");
+    }
+}
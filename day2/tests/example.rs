@@ -0,0 +1,20 @@
+#[test]
+fn example_guide() {
+    let rounds = day2::parse_rounds(include_str!("../test")).unwrap();
+    assert_eq!(rounds.len(), 3);
+    assert_eq!(day2::total_score(&rounds), 15);
+}
+
+#[test]
+fn example_guide_with_results() {
+    let guide = day2::parse_guide(include_str!("../test")).unwrap();
+    let rounds = guide
+        .iter()
+        .map(|r| r.decode(&day2::AsResult))
+        .collect::<Vec<_>>();
+    assert_eq!(day2::total_score(&rounds), 12);
+    assert_eq!(
+        day2::total_scores(&guide, [&day2::AsShape, &day2::AsResult]),
+        [15, 12]
+    );
+}
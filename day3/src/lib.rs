@@ -0,0 +1,59 @@
+//! Day 3: Rucksack Reorganization.
+
+#![warn(missing_docs)]
+
+use parser::ParseError;
+use std::collections::BTreeSet;
+
+/// Find the item that appears in both compartments of a rucksack.
+pub fn find_shared_item(l: &str) -> Result<char, &'static str> {
+    let items = l.chars().collect::<Vec<char>>();
+    if items.len() % 2 != 0 {
+        return Err("Compartments do not hold the same number of items");
+    }
+    let (bag1, bag2) = items.split_at(items.len() / 2);
+
+    let mut seen_in_bag1 = BTreeSet::new();
+    bag1.iter().for_each(|c| {
+        seen_in_bag1.insert(c);
+    });
+
+    let common = bag2
+        .iter()
+        .copied()
+        .filter(|c| seen_in_bag1.contains(c))
+        .collect::<Vec<char>>();
+
+    if !common.is_empty() {
+        Ok(common[0])
+    } else {
+        Err("No item in both compartments")
+    }
+}
+
+/// Priority of an item: `a`-`z` are 1 to 26 and `A`-`Z` are 27 to 52, anything else is not an
+/// item and scores 0.
+pub fn score(c: &char) -> u32 {
+    match c {
+        'a'..='z' => *c as u32 - ('a' as u32) + 1,
+        'A'..='Z' => *c as u32 - ('A' as u32) + 27,
+        _ => 0,
+    }
+}
+
+/// Sum of the priorities of the shared item of every rucksack, one rucksack per line.
+pub fn total_priority(content: &str) -> Result<u32, ParseError> {
+    content
+        .lines()
+        .enumerate()
+        .map(|(n, l)| {
+            let shared = find_shared_item(l).map_err(|e| ParseError {
+                line: n + 1,
+                column: 1,
+                expected: "a rucksack with one item in both compartments",
+                found: format!("{l:?} ({e})"),
+            })?;
+            Ok(score(&shared))
+        })
+        .sum()
+}
@@ -0,0 +1,22 @@
+#[test]
+fn example_rucksacks() {
+    assert_eq!(day3::find_shared_item("vJrwpWtwJgWrhcsFMMfFFhFp"), Ok('p'));
+    assert_eq!(day3::total_priority(include_str!("../test")), Ok(157));
+}
+
+#[test]
+fn odd_rucksacks() {
+    assert!(day3::find_shared_item("abc").is_err());
+    assert_eq!(day3::find_shared_item("éaéb"), Ok('é'));
+    assert_eq!(day3::score(&'é'), 0);
+    assert_eq!(day3::total_priority("!a!b\n"), Ok(0));
+}
+
+#[test]
+fn locate_bad_rucksacks() {
+    let e = day3::total_priority("vJrwpWtwJgWrhcsFMMfFFhFp\nabc\n").unwrap_err();
+    assert_eq!((e.line, e.column), (2, 1));
+    let e = day3::total_priority("aa\nab\n").unwrap_err();
+    assert_eq!(e.line, 2);
+    assert!(e.found.contains("No item in both compartments"), "{e}");
+}
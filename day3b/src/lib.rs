@@ -0,0 +1,46 @@
+//! Day 3 part two: find the badge shared by each group of three elves.
+
+#![warn(missing_docs)]
+
+pub mod stringintersection;
+
+use stringintersection::string_intersection;
+
+/// Priority of an item: `a`-`z` are 1 to 26 and `A`-`Z` are 27 to 52, anything else is not an
+/// item and scores 0.
+pub fn score(c: &char) -> u32 {
+    match c {
+        'a'..='z' => *c as u32 - ('a' as u32) + 1,
+        'A'..='Z' => *c as u32 - ('A' as u32) + 27,
+        _ => 0,
+    }
+}
+
+/// Sum of the priorities of the badge of every group of three elves, each group sharing
+/// exactly one item.
+pub fn badges_priority(content: &str) -> Result<u32, String> {
+    let lines = content.lines().collect::<Vec<&str>>();
+
+    let mut total = 0;
+    for (n, group) in lines.chunks(3).enumerate() {
+        let first_line = 3 * n + 1;
+        if group.len() < 3 {
+            return Err(format!(
+                "Group {} (line {first_line} onward) only has {} elves out of 3",
+                n + 1,
+                group.len()
+            ));
+        }
+        let intersection = string_intersection(&group.to_vec());
+        if intersection.len() != 1 {
+            return Err(format!(
+                "Group {} (lines {first_line}-{}) shares {} items instead of one",
+                n + 1,
+                first_line + 2,
+                intersection.len()
+            ));
+        }
+        total += intersection.iter().map(score).sum::<u32>();
+    }
+    Ok(total)
+}
@@ -1,3 +1,5 @@
+//! Items shared by several rucksacks.
+
 use std::collections::HashSet;
 
 fn string_to_set(s: &str) -> HashSet<char> {
@@ -8,15 +10,16 @@ fn string_to_set(s: &str) -> HashSet<char> {
     set
 }
 
+/// Characters present in all the strings.
 pub fn string_intersection(strings: &Vec<&str>) -> HashSet<char> {
-    if strings.len() == 0 {
+    if strings.is_empty() {
         return HashSet::new();
     }
     let mut it = strings.iter();
     let mut result = string_to_set(it.next().unwrap());
 
-    while let Some(e) = it.next() {
-        result = result.intersection(&string_to_set(*e)).cloned().collect()
+    for e in it {
+        result = result.intersection(&string_to_set(e)).cloned().collect()
     }
 
     result
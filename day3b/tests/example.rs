@@ -0,0 +1,24 @@
+#[test]
+fn example_badges() {
+    assert_eq!(day3b::badges_priority(include_str!("../test")), Ok(70));
+}
+
+#[test]
+fn groups_without_a_badge() {
+    assert_eq!(
+        day3b::badges_priority("abc\nbcd\ncde\nabc\ncde\nefg\n"),
+        Err("Group 2 (lines 4-6) shares 0 items instead of one".to_string())
+    );
+    assert_eq!(
+        day3b::badges_priority("abc\nabd\nabe\n"),
+        Err("Group 1 (lines 1-3) shares 2 items instead of one".to_string())
+    );
+}
+
+#[test]
+fn incomplete_groups() {
+    assert_eq!(
+        day3b::badges_priority("abc\nade\nafg\nxy\n"),
+        Err("Group 2 (line 4 onward) only has 1 elves out of 3".to_string())
+    );
+}
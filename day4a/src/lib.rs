@@ -0,0 +1,24 @@
+//! Day 4: Camp Cleanup, find the pairs where one assignment fully contains the other.
+
+#![warn(missing_docs)]
+
+use parser::{number, parse_lines, separated_pair, tag, PResult, ParseError};
+
+/// `2-4,6-8`
+pub fn assignment(i: &str) -> PResult<'_, ((i32, i32), (i32, i32))> {
+    let range = || separated_pair(number, tag("-"), number);
+    separated_pair(range(), tag(","), range())(i)
+}
+
+/// Number of pairs where one assignment fully contains the other.
+pub fn count_contained(content: &str) -> Result<usize, ParseError> {
+    let assignments = parse_lines(assignment, content)?;
+
+    Ok(assignments.iter().filter(|p| fully_contains(p)).count())
+}
+
+/// True if one of the assignments covers all the sections of the other one.
+pub fn fully_contains((s1, s2): &((i32, i32), (i32, i32))) -> bool {
+    let ((a1, b1), (a2, b2)) = (s1, s2);
+    a1 <= a2 && b2 <= b1 || a2 <= a1 && b1 <= b2
+}
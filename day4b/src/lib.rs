@@ -0,0 +1,24 @@
+//! Day 4 part two: find the pairs of assignments that overlap.
+
+#![warn(missing_docs)]
+
+use parser::{number, parse_lines, separated_pair, tag, PResult, ParseError};
+
+/// `2-4,6-8`
+pub fn assignment(i: &str) -> PResult<'_, ((i32, i32), (i32, i32))> {
+    let range = || separated_pair(number, tag("-"), number);
+    separated_pair(range(), tag(","), range())(i)
+}
+
+/// Number of pairs where the assignments overlap.
+pub fn count_overlapping(content: &str) -> Result<usize, ParseError> {
+    let assignments = parse_lines(assignment, content)?;
+
+    Ok(assignments.iter().filter(|p| overlaps(p)).count())
+}
+
+/// True if the two assignments share at least one section.
+pub fn overlaps((s1, s2): &((i32, i32), (i32, i32))) -> bool {
+    let ((a1, b1), (a2, b2)) = (s1, s2);
+    a1 <= b2 && a2 <= b1
+}
@@ -0,0 +1,10 @@
+#[test]
+fn example_assignments() {
+    assert_eq!(day4b::count_overlapping(include_str!("../test")), Ok(4));
+}
+
+#[test]
+fn huge_assignments() {
+    let pairs = "0-2147483647,1-1\n0-1000000000,1-1\n5-6,7-2147483647\n";
+    assert_eq!(day4b::count_overlapping(pairs), Ok(2));
+}
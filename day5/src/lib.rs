@@ -0,0 +1,146 @@
+//! Day 5: Supply Stacks.
+
+#![warn(missing_docs)]
+
+use parser::{
+    columns, delimited, eof, lines, map, number, one_of, opt, pair, parse_all, preceded,
+    separated_list, separated_pair, spaces, spaces1, tag, terminated, PResult, ParseError,
+};
+use serde::{Deserialize, Serialize};
+
+/// The stacks of crates, bottom crate first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Dock {
+    /// The stacks, numbered from 1 in the move orders.
+    pub stacks: Vec<Vec<char>>,
+}
+
+/// A crate `[A]` or an empty slot, in a 4 characters wide column.
+fn crate_slot(i: &str) -> PResult<'_, Option<char>> {
+    terminated(
+        opt(delimited(
+            tag("["),
+            one_of("ABCDEFGHIJKLMNOPQRSTUVWXYZ"),
+            tag("]"),
+        )),
+        spaces,
+    )(i)
+}
+
+fn crate_row(i: &str) -> PResult<'_, Vec<Option<char>>> {
+    let (rest, cells) = columns(4)(i)?;
+    let slots = cells
+        .into_iter()
+        .map(|cell| terminated(crate_slot, eof)(cell).map(|(_, c)| c))
+        .collect::<Result<Vec<Option<char>>, _>>()?;
+    Ok((rest, slots))
+}
+
+/// ` 1   2   3 `
+fn stack_labels(i: &str) -> PResult<'_, Vec<usize>> {
+    delimited(spaces, separated_list(number, spaces1), spaces)(i)
+}
+
+/// The drawing of the stacks, closed by the line with the stack numbers.
+pub fn dock(i: &str) -> PResult<'_, Dock> {
+    let mut rows = vec![];
+    let mut i = i;
+    let (rest, labels) = loop {
+        if let Ok(done) = stack_labels(i) {
+            break done;
+        }
+        let (rest, row) = terminated(crate_row, tag("\n"))(i)?;
+        rows.push(row);
+        i = rest;
+    };
+
+    let mut stacks: Vec<Vec<char>> = vec![Vec::new(); labels.len()];
+    for row in rows.iter().rev() {
+        for (stack, slot) in stacks.iter_mut().zip(row) {
+            if let Some(c) = slot {
+                stack.push(*c)
+            }
+        }
+    }
+    Ok((rest, Dock { stacks }))
+}
+
+impl TryFrom<&str> for Dock {
+    type Error = ParseError;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        parse_all(dock, value)
+    }
+}
+
+/// Move `qty` crates from stack `from` to stack `to` (stacks are numbered from 1).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MoveOrder {
+    /// Number of crates to move.
+    pub qty: usize,
+    /// Stack to take them from.
+    pub from: usize,
+    /// Stack to put them on.
+    pub to: usize,
+}
+
+/// `move 1 from 2 to 1`
+pub fn move_order(i: &str) -> PResult<'_, MoveOrder> {
+    map(
+        pair(
+            pair(
+                preceded(tag("move "), number),
+                preceded(tag(" from "), number),
+            ),
+            preceded(tag(" to "), number),
+        ),
+        |((qty, from), to)| MoveOrder { qty, from, to },
+    )(i)
+}
+
+impl TryFrom<&str> for MoveOrder {
+    type Error = ParseError;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        parse_all(move_order, value)
+    }
+}
+
+impl Dock {
+    /// For first part of puzzle: the CrateMover 9000 moves crates one at a time.
+    pub fn run9000(&mut self, m: &MoveOrder) -> Option<()> {
+        let (from, to) = (m.from.checked_sub(1)?, m.to.checked_sub(1)?);
+        self.stacks.get(to)?;
+        // Check the quantity first: moving a crate onto its own stack never runs out of crates.
+        if self.stacks.get(from)?.len() < m.qty {
+            return None;
+        }
+        for _c in 0..m.qty {
+            let popped = self.stacks.get_mut(from)?.pop()?;
+            self.stacks.get_mut(to)?.push(popped);
+        }
+        Some(())
+    }
+
+    /// For second part of puzzle: the CrateMover 9001 moves all the crates at once.
+    pub fn run9001(&mut self, m: &MoveOrder) -> Option<()> {
+        let (from, to) = (m.from.checked_sub(1)?, m.to.checked_sub(1)?);
+        self.stacks.get(to)?;
+        let from_stack = self.stacks.get_mut(from)?;
+        let moved = from_stack.split_off(from_stack.len().checked_sub(m.qty)?);
+        self.stacks[to].extend(moved);
+        Some(())
+    }
+
+    /// The crate on top of each stack.
+    pub fn tops(&self) -> String {
+        self.stacks
+            .iter()
+            .map(|stack| stack.last().unwrap_or(&' ').to_string())
+            .collect::<Vec<String>>()
+            .join("")
+    }
+}
+
+/// Split the puzzle input into the starting dock and the list of move orders.
+pub fn parse(input: &str) -> Result<(Dock, Vec<MoveOrder>), ParseError> {
+    parse_all(separated_pair(dock, tag("\n\n"), lines(move_order)), input)
+}
@@ -0,0 +1,35 @@
+use day5::parse;
+
+#[test]
+fn crate_mover_9000() {
+    let (mut dock, moves) = parse(include_str!("../test")).unwrap();
+    for m in &moves {
+        dock.run9000(m);
+    }
+    assert_eq!(dock.tops(), "CMZ");
+}
+
+#[test]
+fn crate_mover_9001() {
+    let (mut dock, moves) = parse(include_str!("../test")).unwrap();
+    for m in &moves {
+        dock.run9001(m);
+    }
+    assert_eq!(dock.tops(), "MCD");
+}
+
+#[test]
+fn invalid_moves() {
+    let (dock, _) = parse(include_str!("../test")).unwrap();
+    let order = |text| day5::MoveOrder::try_from(text).unwrap();
+    for m in [
+        "move 4 from 2 to 1",
+        "move 1 from 0 to 1",
+        "move 1 from 2 to 0",
+        "move 1 from 2 to 4",
+        "move 100000000000 from 1 to 1",
+    ] {
+        assert_eq!(dock.clone().run9000(&order(m)), None, "{m}");
+        assert_eq!(dock.clone().run9001(&order(m)), None, "{m}");
+    }
+}
@@ -0,0 +1,28 @@
+//! Day 6: Tuning Trouble.
+
+#![warn(missing_docs)]
+
+use std::collections::HashSet;
+
+// 6a start-of-packet
+// const prefix_size: usize = 4;
+// 6b start-of-message
+/// Length of the start-of-message marker.
+pub const PREFIX_SIZE: usize = 14;
+
+/// Index of the first character after a run of `PREFIX_SIZE` distinct characters.
+pub fn process_buf(buf: &str) -> Result<usize, &'static str> {
+    find_marker(buf, PREFIX_SIZE)
+}
+
+/// Index of the first character after a run of `size` distinct characters.
+pub fn find_marker(buf: &str, size: usize) -> Result<usize, &'static str> {
+    let chars = buf.chars().collect::<Vec<char>>();
+    for (start, window) in chars.windows(size.max(1)).enumerate() {
+        let set: HashSet<&char> = window.iter().collect();
+        if set.len() == size {
+            return Ok(start + size);
+        }
+    }
+    Err("Did not find four distinct letters in a row.")
+}
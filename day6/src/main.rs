@@ -1,22 +1,7 @@
-use std::collections::HashSet;
+use day6::process_buf;
 use std::env;
 use std::fs;
 
-// 6a start-of-packet
-// const prefix_size: usize = 4;
-// 6b start-of-message
-const PREFIX_SIZE: usize = 14;
-
-fn process_buf(buf: &str) -> Result<usize, &'static str> {
-    for i in PREFIX_SIZE..buf.len() {
-        let set: HashSet<char> = buf[i - PREFIX_SIZE..i].chars().collect();
-        if set.len() == PREFIX_SIZE {
-            return Ok(i);
-        }
-    }
-    Err("Did not find four distinct letters in a row.")
-}
-
 fn main() -> Result<(), &'static str> {
     let args = env::args().collect::<Vec<String>>();
     let filepath_arg = args.get(1);
@@ -27,9 +12,9 @@ fn main() -> Result<(), &'static str> {
             Ok(buf) => {
                 let index = process_buf(&buf)?;
                 println!("BEEP BEEP - Data starts at {index}");
-                return Ok(());
+                Ok(())
             }
-            Err(_) => Err::<(), &str>(&"Error reading file content."),
+            Err(_) => Err::<(), &str>("Error reading file content."),
         }
     } else {
         Err("Please provide one argument with the file containing the data.")
@@ -0,0 +1,11 @@
+#[test]
+fn start_of_message() {
+    assert_eq!(day6::process_buf(include_str!("../test")), Ok(19));
+}
+
+#[test]
+fn markers_of_any_character() {
+    assert_eq!(day6::find_marker("ééabcd", 4), Ok(5));
+    assert_eq!(day6::find_marker("abcd", 4), Ok(4));
+    assert!(day6::find_marker("aaé", 4).is_err());
+}
@@ -0,0 +1,189 @@
+//! The directory tree rebuilt from the terminal transcript.
+
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::listing::{self, InputLine};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+/// A directory: the size of the files it directly contains and its subdirectories.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FileTree {
+    subdirectories: RefCell<HashMap<String, Rc<FileTree>>>,
+    filesize: Cell<usize>,
+}
+
+impl FileTree {
+    /// An empty directory.
+    pub fn from() -> FileTree {
+        FileTree {
+            subdirectories: RefCell::from(HashMap::new()),
+            filesize: Cell::from(0),
+        }
+    }
+    /// Print the tree with the total size of each directory.
+    pub fn pretty(&self) {
+        Self::pretty_i("/", self, "".to_owned());
+    }
+    fn pretty_i(name: &str, tree: &FileTree, indent: String) {
+        println!(
+            "{indent}{name} \t\t{} ({})",
+            tree.size(),
+            tree.filesize.get()
+        );
+        tree.subdirectories
+            .borrow()
+            .iter()
+            .for_each(|(n, t)| Self::pretty_i(n, t, indent.clone() + "  "))
+    }
+    /// Total size of the directory, including all its subdirectories. Sizes too big to add up
+    /// are `usize::MAX`, which is more than any disk.
+    pub fn size(&self) -> usize {
+        self.subdirectories
+            .borrow()
+            .values()
+            .fold(self.filesize.get(), |size, t| size.saturating_add(t.size()))
+    }
+
+    fn find_dirs_i(&self, v: &mut Vec<usize>) {
+        self.subdirectories.borrow().values().for_each(|t| {
+            v.push(t.size());
+            t.find_dirs_i(v);
+        })
+    }
+    /// Size of every directory below this one.
+    pub fn find_dirs(&self) -> Vec<usize> {
+        let mut v = vec![];
+        self.find_dirs_i(&mut v);
+        v
+    }
+    /// Sum of the sizes of the directories of at most 100000.
+    pub fn less_than_10000(&self) -> usize {
+        self.find_dirs()
+            .iter()
+            .map(|s| if *s <= 100000 { s } else { &0 })
+            .sum()
+    }
+}
+
+/// Deepest directory accepted: sizes are added up recursively, one stack frame per level.
+pub const MAX_DEPTH: usize = 1000;
+
+/// Replay the commands of the transcript to rebuild the directory tree.
+pub fn build_tree(line_it: &mut std::slice::Iter<InputLine>) -> Result<Rc<FileTree>, &'static str> {
+    let root = Rc::from(FileTree::from());
+
+    let mut dstack = vec![root.clone()];
+
+    for line in line_it.by_ref() {
+        match line {
+            InputLine::CdRoot => {
+                dstack = vec![root.clone()];
+            }
+            InputLine::CdFolder { s } => match s.as_str() {
+                ".." => {
+                    dstack.pop();
+                }
+                _ => {
+                    let mut subdir = Rc::from(FileTree::from());
+                    if let Some(cwd) = dstack.last() {
+                        let mut subdirs = cwd.subdirectories.borrow_mut();
+                        if let Some(subd) = subdirs.get(s) {
+                            // Revisiting already visited folder
+                            subdir = subd.clone();
+                        } else {
+                            subdirs.insert(s.clone(), subdir.clone());
+                        }
+                    }
+                    dstack.push(subdir);
+                    if dstack.len() > MAX_DEPTH {
+                        return Err("The transcript goes more than 1000 directories deep");
+                    }
+                }
+            },
+            InputLine::ListingOutput(listing::ListingOutput::File { name: _, size }) => {
+                if let Some(cwd) = dstack.last() {
+                    let new_size = cwd.filesize.get().saturating_add(*size);
+                    cwd.filesize.set(new_size);
+                }
+            }
+            InputLine::LsCmd => {
+                if let Some(cwd) = dstack.last() {
+                    cwd.filesize.set(0);
+                }
+            }
+            InputLine::ListingOutput(listing::ListingOutput::Directory { name: _ }) => (
+                /* Ignore directory listing. We will only include them if user visits them. */
+            ),
+        }
+    }
+
+    Ok(root.clone())
+}
+
+/// Size of the disk.
+pub const TOTAL_DISK_SPACE: usize = 70000000;
+/// Free space the update needs.
+pub const UPDATE_SIZE: usize = 30000000;
+
+impl FileTree {
+    /// Space left on the disk, `None` when the files do not fit on it.
+    pub fn free_space(&self) -> Option<usize> {
+        TOTAL_DISK_SPACE.checked_sub(self.size())
+    }
+
+    /// Space we need to free to be able to install the update, `None` when the files do not
+    /// fit on the disk.
+    pub fn reclaim_min(&self) -> Option<usize> {
+        Some(UPDATE_SIZE.saturating_sub(self.free_space()?))
+    }
+
+    /// Smallest directory that will give enough space for the update once deleted.
+    pub fn smallest_deletable(&self) -> Option<usize> {
+        let reclaim_min = self.reclaim_min()?;
+        self.find_dirs()
+            .iter()
+            .filter(|d| **d > reclaim_min)
+            .sorted()
+            .cloned()
+            .next()
+    }
+}
+
+#[test]
+fn test_data() {
+    let data = std::fs::read_to_string("test").expect("cannot read test file");
+
+    let parsed = crate::parse_transcript(&data).expect("cannot parse test file");
+    let tree = build_tree(&mut parsed.iter()).expect("cannot build tree");
+
+    assert_eq!(tree.less_than_10000(), 95437);
+}
+
+#[test]
+fn visit_same_dir_twice() {
+    let data = std::fs::read_to_string("test_double").expect("cannot read test file");
+
+    let parsed = crate::parse_transcript(&data).expect("cannot parse test file");
+    let tree = build_tree(&mut parsed.iter()).expect("cannot build tree");
+    assert_eq!(tree.less_than_10000(), 95437);
+}
+
+#[test]
+fn overfilled_disk() {
+    let parsed = crate::parse_transcript("$ cd /\n$ ls\n80000000 a\n").unwrap();
+    let tree = build_tree(&mut parsed.iter()).expect("cannot build tree");
+    assert_eq!(tree.free_space(), None);
+    assert_eq!(tree.smallest_deletable(), None);
+}
+
+#[test]
+fn deep_transcript() {
+    let parsed = crate::parse_transcript(&"$ cd a\n".repeat(200000)).unwrap();
+    assert!(build_tree(&mut parsed.iter()).is_err());
+    let parsed = crate::parse_transcript(&"$ cd a\n".repeat(MAX_DEPTH - 1)).unwrap();
+    assert!(build_tree(&mut parsed.iter()).is_ok());
+}
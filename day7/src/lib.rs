@@ -0,0 +1,20 @@
+//! Day 7: No Space Left On Device.
+
+#![warn(missing_docs)]
+
+pub mod filetree;
+pub mod listing;
+
+use listing::{input_line, InputLine};
+use parser::{parse_lines, ParseError};
+
+/// Parse a terminal transcript, one command or line of `ls` output per line.
+pub fn parse_transcript(data: &str) -> Result<Vec<InputLine>, ParseError> {
+    parse_lines(input_line, data)
+}
+
+#[test]
+fn locate_errors() {
+    let e = parse_transcript("$ cd /\n$ ls\n12\n").unwrap_err();
+    assert_eq!((e.line, e.column), (3, 3));
+}
@@ -1,45 +1,84 @@
-#[derive(Debug, PartialEq, Eq)]
+//! The lines of the terminal transcript.
+
+use parser::{
+    eof, map, number, or, parse_all, preceded, separated_pair, tag, terminated, value, word,
+    PResult, ParseError,
+};
+use serde::{Deserialize, Serialize};
+
+/// One entry printed by `ls`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ListingOutput {
-    File { name: String, size: usize },
-    Directory { name: String },
+    /// `14848514 b.txt`
+    File {
+        /// Name of the file.
+        name: String,
+        /// Size of the file.
+        size: usize,
+    },
+    /// `dir a`
+    Directory {
+        /// Name of the directory.
+        name: String,
+    },
 }
 
-#[derive(Debug, PartialEq, Eq)]
+/// One line of the transcript.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum InputLine {
+    /// `$ cd /`
     CdRoot,
-    CdFolder { s: String },
+    /// `$ cd a` or `$ cd ..`
+    CdFolder {
+        /// The directory to go into, `..` for the parent.
+        s: String,
+    },
+    /// `$ ls`
     LsCmd,
+    /// A line printed by `ls`.
     ListingOutput(ListingOutput),
 }
 
 // IMPROVEMENTS:
 // Avoid copying the input data into strings... - keep using reference and use lifetimes.
 
-impl TryFrom<&str> for InputLine {
-    type Error = &'static str;
-
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let fields: Vec<&str> = value.split_whitespace().collect();
-        let parts = (fields.get(0), fields.get(1), fields.get(2));
+/// One line of the terminal: either a command we typed or the output of `ls`.
+pub fn input_line(i: &str) -> PResult<'_, InputLine> {
+    or(
+        or(
+            value(InputLine::LsCmd, tag("$ ls")),
+            or(
+                value(InputLine::CdRoot, terminated(tag("$ cd /"), eof)),
+                map(preceded(tag("$ cd "), word), |path| InputLine::CdFolder {
+                    s: path.to_string(),
+                }),
+            ),
+        ),
+        map(listing_output, InputLine::ListingOutput),
+    )(i)
+}
 
-        match parts {
-            (Some(&"$"), Some(&"ls"), _) => Ok(InputLine::LsCmd),
-            (Some(&"$"), Some(&"cd"), Some(&"/")) => Ok(InputLine::CdRoot),
-            (Some(&"$"), Some(&"cd"), Some(path)) => Ok(InputLine::CdFolder {
-                s: path.to_string(),
-            }),
-            (Some(&"dir"), Some(name), _) => {
-                Ok(InputLine::ListingOutput(ListingOutput::Directory {
-                    name: name.to_string(),
-                }))
+fn listing_output(i: &str) -> PResult<'_, ListingOutput> {
+    or(
+        map(preceded(tag("dir "), word), |name| {
+            ListingOutput::Directory {
+                name: name.to_string(),
             }
-            (Some(size), Some(name), _) => Ok(InputLine::ListingOutput(ListingOutput::File {
+        }),
+        map(separated_pair(number, tag(" "), word), |(size, name)| {
+            ListingOutput::File {
                 name: name.to_string(),
-                size: size.parse().map_err(|_| "Unable to parse size")?,
-            })),
+                size,
+            }
+        }),
+    )(i)
+}
 
-            _ => Err("Invalid input line."),
-        }
+impl TryFrom<&str> for InputLine {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        parse_all(input_line, value)
     }
 }
 
@@ -47,12 +86,12 @@ impl TryFrom<&str> for InputLine {
 fn parse_ls() {
     match "$ ls".try_into() as Result<InputLine, _> {
         Ok(x) => assert!(x == InputLine::LsCmd),
-        Err(e) => assert!(false, "{e:?}"),
+        Err(e) => panic!("{e:?}"),
     }
 }
 #[test]
 fn convert_listing() {
-    let lines: Vec<InputLine> = vec!["$ cd /", "$ ls", "dir a", "111 b.txt"]
+    let lines: Vec<InputLine> = ["$ cd /", "$ ls", "dir a", "111 b.txt"]
         .iter()
         .cloned()
         .filter_map(|l| l.try_into().ok())
@@ -0,0 +1,31 @@
+//! Day 9: Rope Bridge.
+
+#![warn(missing_docs)]
+
+pub mod point;
+pub mod rope;
+
+use parser::{parse_lines, ParseError};
+use point::{head_move, Move};
+
+/// Most steps the head may take over all the moves: the tail remembers every position it
+/// visits.
+pub const MAX_STEPS: i64 = 10_000_000;
+
+/// Read the list of head movements.
+pub fn parse_moves(data: &str) -> Result<Vec<Move>, ParseError> {
+    let moves = parse_lines(head_move, data)?;
+    let mut steps = 0;
+    for (n, m) in moves.iter().enumerate() {
+        steps += i64::from(m.1.max(0));
+        if steps > MAX_STEPS {
+            return Err(ParseError {
+                line: n + 1,
+                column: 3,
+                expected: "at most 10000000 steps in total",
+                found: m.1.to_string(),
+            });
+        }
+    }
+    Ok(moves)
+}
@@ -1,68 +1,85 @@
+//! Points of the grid and the moves of the head.
+
+use parser::{map, number, one_of, parse_all, separated_pair, tag, PResult, ParseError};
+use serde::{Deserialize, Serialize};
 use std::f32::consts::PI;
 use std::ops;
 
-#[derive(PartialEq, Eq, Debug, Clone, Copy, Hash)]
+/// A position on the grid, `y` going up.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Hash, Serialize, Deserialize)]
 pub struct Point(i32, i32);
-#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+/// The difference between two points.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct Vector(pub i32, pub i32);
 impl Vector {
+    /// Euclidean length.
     pub fn length(&self) -> f32 {
         (((self.0).pow(2) + (self.1).pow(2)) as f32).sqrt()
     }
+    /// Direction in rounded degrees: 0 towards a greater `x`, 90 towards a smaller `y`.
     pub fn angle(&self) -> i32 {
         // We use the (0,0) in top left convention so we need to take the opposite of the y coordinates
-        let angle = (self.1 as f32).atan2(self.0 as f32);
+        let angle = (-self.1 as f32).atan2(self.0 as f32);
 
         // Convert to rounded degrees because that will easier for this project
-        let degrees = (360.0 * angle / (2.0 * PI)).round() as i32;
-        degrees
+        (360.0 * angle / (2.0 * PI)).round() as i32
     }
 }
 
 impl Point {
+    /// The point at `(x, y)`.
     pub fn new(x: i32, y: i32) -> Self {
         Point(x, y)
     }
+    /// Horizontal coordinate.
     pub fn x(&self) -> i32 {
         self.0
     }
+    /// Vertical coordinate.
     pub fn y(&self) -> i32 {
         self.1
     }
+    /// The vector going from `self` to `other`.
     pub fn vec_to(&self, other: &Point) -> Vector {
         Vector(other.0 - self.0, other.1 - self.1)
     }
 }
 
-#[derive(Debug)]
+/// Move the head `.1` steps in direction `.0`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct Move(pub Dir, pub i32);
 
+/// `R 4`
+pub fn head_move(i: &str) -> PResult<'_, Move> {
+    let dir = map(one_of("RLUD"), |c| match c {
+        'R' => Dir::R,
+        'L' => Dir::L,
+        'U' => Dir::U,
+        _ => Dir::D,
+    });
+    map(separated_pair(dir, tag(" "), number), |(d, len)| {
+        Move(d, len)
+    })(i)
+}
+
 impl TryFrom<&str> for Move {
-    type Error = &'static str;
+    type Error = ParseError;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        if let Some((l, r)) = value.split_once(" ") {
-            Ok(Move(
-                match l {
-                    "R" => Dir::R,
-                    "L" => Dir::L,
-                    "U" => Dir::U,
-                    "D" => Dir::D,
-                    _ => return Err("Invalid movement command"),
-                },
-                r.parse().map_err(|_| "Invalid movement len")?,
-            ))
-        } else {
-            Err("Invalid line")
-        }
+        parse_all(head_move, value)
     }
 }
 
-#[derive(Debug)]
+/// Direction of a move.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum Dir {
+    /// Up, `y` grows.
     U,
+    /// Left, `x` shrinks.
     L,
+    /// Down, `y` shrinks.
     D,
+    /// Right, `x` grows.
     R,
 }
 
@@ -0,0 +1,27 @@
+use day9::rope::Rope;
+
+fn tail_visits(knots: usize, data: &str) -> usize {
+    let mut rope = Rope::new(knots, 0, 0);
+    for m in day9::parse_moves(data).unwrap() {
+        rope.move_head(m);
+    }
+    rope.tail_visits()
+}
+
+#[test]
+fn short_rope() {
+    assert_eq!(tail_visits(2, include_str!("../test")), 13);
+}
+
+#[test]
+fn long_rope() {
+    assert_eq!(tail_visits(10, include_str!("../test")), 1);
+    assert_eq!(tail_visits(10, include_str!("../test2")), 36);
+}
+
+#[test]
+fn too_many_steps() {
+    let e = day9::parse_moves("R 4\nR 2000000000\n").unwrap_err();
+    assert_eq!((e.line, e.column), (2, 3));
+    assert!(day9::parse_moves(&"R 1000000\n".repeat(10)).is_ok());
+}
@@ -0,0 +1,99 @@
+//! The bodies of the fuzz targets of `fuzz/`, one per day, feeding arbitrary bytes to every
+//! parser, and one solving every day. They only have to return: any panic is a bug.
+//!
+//! Fuzz a day with `cargo +nightly fuzz run day3` from the root of the repository. The seeds
+//! of `fuzz/corpus`, crashers included, are replayed by `tests/corpus.rs` so they stay fixed:
+//! copy a new crash from `fuzz/artifacts` to the corpus as `crash-<what>` once it is fixed.
+
+use std::borrow::Cow;
+
+fn text(data: &[u8]) -> Cow<'_, str> {
+    String::from_utf8_lossy(data)
+}
+
+pub fn day1(data: &[u8]) {
+    let _ = day1a::parse_diets(&text(data));
+}
+
+pub fn day2(data: &[u8]) {
+    let input = text(data);
+    let _ = day2::parse_rounds(&input);
+    let _ = day2::parse_guide(&input);
+    let _ = day2::game::Game::parse(&input);
+    let _ = day2::game::Game::classic().read_guide(&input);
+    for line in input.lines() {
+        let _ = day2::RPSRound::read(line);
+    }
+}
+
+pub fn day3(data: &[u8]) {
+    let input = text(data);
+    for line in input.lines() {
+        let _ = day3::find_shared_item(line);
+    }
+    let _ = day3::total_priority(&input);
+    let _ = day3b::badges_priority(&input);
+}
+
+pub fn day4(data: &[u8]) {
+    let _ = parser::parse_lines(day4a::assignment, &text(data));
+}
+
+pub fn day5(data: &[u8]) {
+    let input = text(data);
+    let _ = day5::parse(&input);
+    let _ = day5::Dock::try_from(input.as_ref());
+    for line in input.lines() {
+        let _ = day5::MoveOrder::try_from(line);
+    }
+}
+
+pub fn day6(data: &[u8]) {
+    let input = text(data);
+    let _ = day6::process_buf(&input);
+    let _ = day6::find_marker(&input, 4);
+}
+
+pub fn day7(data: &[u8]) {
+    if let Ok(lines) = day7::parse_transcript(&text(data)) {
+        let _ = day7::filetree::build_tree(&mut lines.iter());
+    }
+}
+
+pub fn day8(data: &[u8]) {
+    let _ = day8::Garden::try_from(text(data).as_ref());
+}
+
+pub fn day9(data: &[u8]) {
+    let _ = day9::parse_moves(&text(data));
+}
+
+pub fn day10(data: &[u8]) {
+    let _ = day10::parse_program(&text(data));
+}
+
+/// Parse and solve both parts of every day registered in the runner, like `aoc run` does.
+pub fn solutions(data: &[u8]) {
+    let input = text(data);
+    // Days still ignoring their input would only solve the same puzzle over and over.
+    for day in aoc::days::DAYS.iter().filter(|d| d.partial.is_none()) {
+        (day.run)(day.year, day.day, &input);
+    }
+}
+
+pub type Target = fn(&[u8]);
+
+/// Every target, by the name of its folder in `fuzz/corpus`.
+pub const TARGETS: &[(&str, Target)] = &[
+    ("day1", day1),
+    ("day2", day2),
+    ("day3", day3),
+    ("day4", day4),
+    ("day5", day5),
+    ("day6", day6),
+    ("day7", day7),
+    ("day8", day8),
+    ("day9", day9),
+    ("day10", day10),
+    ("solutions", solutions),
+];
@@ -0,0 +1,80 @@
+//! Replay the fuzz corpus, and mutations of it, through every target.
+
+use fuzz_targets::TARGETS;
+use rng::Rng;
+use std::fs;
+use std::path::Path;
+
+/// A number in `0..n`, seeded so the mutations are the same on every run.
+fn below(rng: &mut Rng, n: usize) -> usize {
+    rng.below(n as u64) as usize
+}
+
+/// Bytes the puzzle inputs are made of, plus a few that are not.
+const ALPHABET: &[u8] =
+    b"0123456789 \n\n-,[]$/.abcdxyzABCXYZLRUDmovefromtoaddxnoopcdlsdir\xc3\xa9\xff!";
+
+fn corpus(target: &str) -> Vec<Vec<u8>> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../fuzz/corpus")
+        .join(target);
+    let mut entries = fs::read_dir(&dir)
+        .unwrap_or_else(|e| panic!("{}: {e}", dir.display()))
+        .map(|e| e.unwrap().path())
+        .collect::<Vec<_>>();
+    entries.sort();
+    entries.iter().map(|p| fs::read(p).unwrap()).collect()
+}
+
+fn mutate(seed: &[u8], rng: &mut Rng) -> Vec<u8> {
+    let mut data = seed.to_vec();
+    for _ in 0..1 + below(rng, 8) {
+        let at = below(rng, data.len() + 1);
+        let byte = ALPHABET[below(rng, ALPHABET.len())];
+        match below(rng, 4) {
+            0 => data.insert(at, byte),
+            1 if at < data.len() => data[at] = byte,
+            2 if at < data.len() => {
+                data.remove(at);
+            }
+            _ => data.truncate(at),
+        }
+    }
+    data
+}
+
+#[test]
+fn replay_the_corpus() {
+    for (name, target) in TARGETS {
+        let seeds = corpus(name);
+        assert!(!seeds.is_empty(), "no corpus for {name}");
+        for seed in &seeds {
+            target(seed);
+        }
+    }
+}
+
+#[test]
+fn mutations_of_the_corpus() {
+    let mut rng = Rng::new(2022);
+    for (name, target) in TARGETS {
+        for seed in corpus(name) {
+            for _ in 0..200 {
+                target(&mutate(&seed, &mut rng));
+            }
+        }
+    }
+}
+
+#[test]
+fn random_bytes() {
+    let mut rng = Rng::new(25);
+    for (_, target) in TARGETS {
+        for _ in 0..500 {
+            let data = (0..below(&mut rng, 64))
+                .map(|_| ALPHABET[below(&mut rng, ALPHABET.len())])
+                .collect::<Vec<u8>>();
+            target(&data);
+        }
+    }
+}
@@ -0,0 +1,427 @@
+//! Tiny parser combinators shared by all the days.
+//!
+//! A parser is any `Fn(&str) -> PResult<T>`: it consumes the start of the input and returns what
+//! is left along with the parsed value. Failures keep a reference to where in the input they
+//! happened so `ParseError` can report the line and column in the original puzzle input.
+
+#![warn(missing_docs)]
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Where a parser gave up and what it was looking for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Failure<'a> {
+    /// The input left when the parser failed.
+    pub at: &'a str,
+    /// What the parser was looking for.
+    pub expected: &'static str,
+}
+
+/// The rest of the input and the parsed value, or where parsing failed.
+pub type PResult<'a, T> = Result<(&'a str, T), Failure<'a>>;
+
+/// Fail at `at`, looking for `expected`.
+pub fn fail<'a, T>(at: &'a str, expected: &'static str) -> PResult<'a, T> {
+    Err(Failure { at, expected })
+}
+
+/// Match a literal string.
+pub fn tag<'a>(t: &'static str) -> impl Fn(&'a str) -> PResult<'a, &'a str> {
+    move |i: &'a str| match i.strip_prefix(t) {
+        Some(rest) => Ok((rest, &i[..t.len()])),
+        None => fail(i, t),
+    }
+}
+
+/// Match one character from the given set.
+pub fn one_of<'a>(chars: &'static str) -> impl Fn(&'a str) -> PResult<'a, char> {
+    move |i: &'a str| match i.chars().next() {
+        Some(c) if chars.contains(c) => Ok((&i[c.len_utf8()..], c)),
+        _ => fail(i, chars),
+    }
+}
+
+/// A single decimal digit.
+pub fn digit(i: &str) -> PResult<'_, u32> {
+    match i.chars().next().and_then(|c| c.to_digit(10)) {
+        Some(d) => Ok((&i[1..], d)),
+        None => fail(i, "digit"),
+    }
+}
+
+/// Zero or more spaces (not newlines).
+pub fn spaces(i: &str) -> PResult<'_, &str> {
+    let end = i.find(|c| c != ' ').unwrap_or(i.len());
+    Ok((&i[end..], &i[..end]))
+}
+
+/// One or more spaces (not newlines).
+pub fn spaces1(i: &str) -> PResult<'_, &str> {
+    match spaces(i)? {
+        (_, "") => fail(i, "space"),
+        ok => Ok(ok),
+    }
+}
+
+/// A run of non-whitespace characters.
+pub fn word(i: &str) -> PResult<'_, &str> {
+    let end = i.find(char::is_whitespace).unwrap_or(i.len());
+    if end == 0 {
+        return fail(i, "word");
+    }
+    Ok((&i[end..], &i[..end]))
+}
+
+/// An optionally signed decimal number.
+pub fn number<T: FromStr>(i: &str) -> PResult<'_, T> {
+    let digits_start = if i.starts_with('-') { 1 } else { 0 };
+    let end = i[digits_start..]
+        .find(|c: char| !c.is_ascii_digit())
+        .map_or(i.len(), |e| e + digits_start);
+    if end == digits_start {
+        return fail(i, "number");
+    }
+    match i[..end].parse() {
+        Ok(n) => Ok((&i[end..], n)),
+        Err(_) => fail(i, "number"),
+    }
+}
+
+/// Succeed only at the end of the input.
+pub fn eof(i: &str) -> PResult<'_, ()> {
+    if i.is_empty() {
+        Ok((i, ()))
+    } else {
+        fail(i, "end of input")
+    }
+}
+
+/// Everything that is left.
+pub fn rest(i: &str) -> PResult<'_, &str> {
+    Ok(("", i))
+}
+
+/// Apply `f` to the value parsed by `p`.
+pub fn map<'a, A, B>(
+    p: impl Fn(&'a str) -> PResult<'a, A>,
+    f: impl Fn(A) -> B,
+) -> impl Fn(&'a str) -> PResult<'a, B> {
+    move |i: &'a str| p(i).map(|(rest, a)| (rest, f(a)))
+}
+
+/// Like `map` but the conversion can be refused, in which case we fail where `p` started.
+pub fn map_opt<'a, A, B>(
+    p: impl Fn(&'a str) -> PResult<'a, A>,
+    f: impl Fn(A) -> Option<B>,
+    expected: &'static str,
+) -> impl Fn(&'a str) -> PResult<'a, B> {
+    move |i: &'a str| {
+        let (rest, a) = p(i)?;
+        match f(a) {
+            Some(b) => Ok((rest, b)),
+            None => fail(i, expected),
+        }
+    }
+}
+
+/// Replace the output of `p` with a fixed value.
+pub fn value<'a, A, B: Clone>(
+    v: B,
+    p: impl Fn(&'a str) -> PResult<'a, A>,
+) -> impl Fn(&'a str) -> PResult<'a, B> {
+    move |i: &'a str| p(i).map(|(rest, _)| (rest, v.clone()))
+}
+
+/// Run `a` then `b`, keeping both values.
+pub fn pair<'a, A, B>(
+    a: impl Fn(&'a str) -> PResult<'a, A>,
+    b: impl Fn(&'a str) -> PResult<'a, B>,
+) -> impl Fn(&'a str) -> PResult<'a, (A, B)> {
+    move |i: &'a str| {
+        let (i, x) = a(i)?;
+        let (i, y) = b(i)?;
+        Ok((i, (x, y)))
+    }
+}
+
+/// Run `a`, `sep` then `b`, keeping the values of `a` and `b`.
+pub fn separated_pair<'a, A, S, B>(
+    a: impl Fn(&'a str) -> PResult<'a, A>,
+    sep: impl Fn(&'a str) -> PResult<'a, S>,
+    b: impl Fn(&'a str) -> PResult<'a, B>,
+) -> impl Fn(&'a str) -> PResult<'a, (A, B)> {
+    move |i: &'a str| {
+        let (i, x) = a(i)?;
+        let (i, _) = sep(i)?;
+        let (i, y) = b(i)?;
+        Ok((i, (x, y)))
+    }
+}
+
+/// Run `a` then `b`, keeping only the output of `b`.
+pub fn preceded<'a, A, B>(
+    a: impl Fn(&'a str) -> PResult<'a, A>,
+    b: impl Fn(&'a str) -> PResult<'a, B>,
+) -> impl Fn(&'a str) -> PResult<'a, B> {
+    move |i: &'a str| {
+        let (i, _) = a(i)?;
+        b(i)
+    }
+}
+
+/// Run `a` then `b`, keeping only the output of `a`.
+pub fn terminated<'a, A, B>(
+    a: impl Fn(&'a str) -> PResult<'a, A>,
+    b: impl Fn(&'a str) -> PResult<'a, B>,
+) -> impl Fn(&'a str) -> PResult<'a, A> {
+    move |i: &'a str| {
+        let (i, x) = a(i)?;
+        let (i, _) = b(i)?;
+        Ok((i, x))
+    }
+}
+
+/// Run `open`, `a` then `close`, keeping the value of `a`.
+pub fn delimited<'a, O, A, C>(
+    open: impl Fn(&'a str) -> PResult<'a, O>,
+    a: impl Fn(&'a str) -> PResult<'a, A>,
+    close: impl Fn(&'a str) -> PResult<'a, C>,
+) -> impl Fn(&'a str) -> PResult<'a, A> {
+    move |i: &'a str| {
+        let (i, _) = open(i)?;
+        let (i, x) = a(i)?;
+        let (i, _) = close(i)?;
+        Ok((i, x))
+    }
+}
+
+/// Try `a` and if it fails, try `b` on the same input.
+pub fn or<'a, T>(
+    a: impl Fn(&'a str) -> PResult<'a, T>,
+    b: impl Fn(&'a str) -> PResult<'a, T>,
+) -> impl Fn(&'a str) -> PResult<'a, T> {
+    move |i: &'a str| a(i).or_else(|_| b(i))
+}
+
+/// Run `p` if it matches, without consuming anything otherwise.
+pub fn opt<'a, T>(
+    p: impl Fn(&'a str) -> PResult<'a, T>,
+) -> impl Fn(&'a str) -> PResult<'a, Option<T>> {
+    move |i: &'a str| match p(i) {
+        Ok((rest, x)) => Ok((rest, Some(x))),
+        Err(_) => Ok((i, None)),
+    }
+}
+
+/// Apply `p` as many times as possible, at least once.
+pub fn many1<'a, T>(
+    p: impl Fn(&'a str) -> PResult<'a, T>,
+) -> impl Fn(&'a str) -> PResult<'a, Vec<T>> {
+    move |i: &'a str| {
+        let (mut i, first) = p(i)?;
+        let mut items = vec![first];
+        while let Ok((rest, x)) = p(i) {
+            if rest.len() == i.len() {
+                break;
+            }
+            items.push(x);
+            i = rest;
+        }
+        Ok((i, items))
+    }
+}
+
+/// One or more `p` separated by `sep`.
+pub fn separated_list<'a, T, S>(
+    p: impl Fn(&'a str) -> PResult<'a, T>,
+    sep: impl Fn(&'a str) -> PResult<'a, S>,
+) -> impl Fn(&'a str) -> PResult<'a, Vec<T>> {
+    move |i: &'a str| {
+        let (mut i, first) = p(i)?;
+        let mut items = vec![first];
+        while let Ok((after_sep, _)) = sep(i) {
+            match p(after_sep) {
+                Ok((rest, x)) => {
+                    items.push(x);
+                    i = rest;
+                }
+                Err(_) => break,
+            }
+        }
+        Ok((i, items))
+    }
+}
+
+/// Consume a whole line and cut it in cells of `width` characters. The last cell can be shorter
+/// when the line has been trimmed.
+pub fn columns<'a>(width: usize) -> impl Fn(&'a str) -> PResult<'a, Vec<&'a str>> {
+    move |i: &'a str| {
+        let end = i.find('\n').unwrap_or(i.len());
+        let mut line = &i[..end];
+        let mut cells = vec![];
+        while !line.is_empty() {
+            let cut = line
+                .char_indices()
+                .nth(width)
+                .map_or(line.len(), |(c, _)| c);
+            cells.push(&line[..cut]);
+            line = &line[cut..];
+        }
+        Ok((&i[end..], cells))
+    }
+}
+
+/// Apply `p` to every remaining line. Each line must be entirely consumed by `p`.
+pub fn lines<'a, T>(
+    p: impl Fn(&'a str) -> PResult<'a, T>,
+) -> impl Fn(&'a str) -> PResult<'a, Vec<T>> {
+    move |i: &'a str| {
+        let mut items = vec![];
+        for l in i.lines() {
+            let (_, x) = terminated(&p, eof)(l)?;
+            items.push(x);
+        }
+        Ok(("", items))
+    }
+}
+
+/// Split the input on blank lines, skipping empty paragraphs.
+pub fn paragraphs(input: &str) -> impl Iterator<Item = &str> {
+    input
+        .split("\n\n")
+        .map(|p| p.trim_matches('\n'))
+        .filter(|p| !p.is_empty())
+}
+
+/// A parse failure located in the source text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Line of the failure, starting at 1.
+    pub line: usize,
+    /// Column of the failure in characters, starting at 1.
+    pub column: usize,
+    /// What the parser was looking for.
+    pub expected: &'static str,
+    /// What it found instead, eg: `"x"` or `end of line`.
+    pub found: String,
+}
+
+impl ParseError {
+    /// Work out the line and column of `failure` in `source`. The failure must point inside
+    /// `source`, which is always the case when the parser was given a slice of it.
+    pub fn locate(source: &str, failure: Failure) -> Self {
+        let offset = (failure.at.as_ptr() as usize)
+            .saturating_sub(source.as_ptr() as usize)
+            .min(source.len());
+        let before = &source[..offset];
+        let line = before.matches('\n').count() + 1;
+        let column = before.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+        let found = match failure.at.split_whitespace().next() {
+            _ if failure.at.is_empty() => "end of input".to_string(),
+            _ if failure.at.starts_with('\n') => "end of line".to_string(),
+            Some(token) => format!("{token:?}"),
+            None => "whitespace".to_string(),
+        };
+        ParseError {
+            line,
+            column,
+            expected: failure.expected,
+            found,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {:?}, found {}",
+            self.line, self.column, self.expected, self.found
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// Run `p` on the whole input, which must be entirely consumed.
+pub fn parse_all<'a, T>(
+    p: impl Fn(&'a str) -> PResult<'a, T>,
+    input: &'a str,
+) -> Result<T, ParseError> {
+    parse_within(p, input, input)
+}
+
+/// Run `p` on `part`, which must be entirely consumed, and locate errors in `source`, the text
+/// `part` was cut from.
+pub fn parse_within<'a, T>(
+    p: impl Fn(&'a str) -> PResult<'a, T>,
+    part: &'a str,
+    source: &str,
+) -> Result<T, ParseError> {
+    terminated(p, eof)(part)
+        .map(|(_, x)| x)
+        .map_err(|f| ParseError::locate(source, f))
+}
+
+/// Run `p` on every line of the input. Errors are located relative to the whole input.
+pub fn parse_lines<'a, T>(
+    p: impl Fn(&'a str) -> PResult<'a, T>,
+    input: &'a str,
+) -> Result<Vec<T>, ParseError> {
+    parse_all(lines(p), input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers() {
+        assert_eq!(number::<i32>("42 rest"), Ok((" rest", 42)));
+        assert_eq!(number::<i32>("-7"), Ok(("", -7)));
+        assert!(number::<u32>("-7").is_err());
+        assert!(number::<i32>("x").is_err());
+    }
+
+    #[test]
+    fn lists() {
+        let p = separated_list(number::<u32>, tag(", "));
+        assert_eq!(p("1, 2, 3"), Ok(("", vec![1, 2, 3])));
+        assert_eq!(p("1, 2, x"), Ok((", x", vec![1, 2])));
+    }
+
+    #[test]
+    fn fixed_width_columns() {
+        let (rest, cells) = columns(4)("[A]     [C]\nnext").unwrap();
+        assert_eq!(cells, vec!["[A] ", "    ", "[C]"]);
+        assert_eq!(rest, "\nnext");
+    }
+
+    #[test]
+    fn split_paragraphs() {
+        let p = paragraphs("1\n2\n\n3\n\n\n").collect::<Vec<&str>>();
+        assert_eq!(p, vec!["1\n2", "3"]);
+    }
+
+    #[test]
+    fn error_location() {
+        let input = "move 1 from 2 to 3\nmove 1 frm 2 to 3\n";
+        let order = pair(
+            preceded(tag("move "), number::<u32>),
+            preceded(tag(" from "), number::<u32>),
+        );
+        let e = parse_lines(
+            terminated(order, preceded(tag(" to "), number::<u32>)),
+            input,
+        )
+        .unwrap_err();
+        assert_eq!((e.line, e.column), (2, 7));
+        assert_eq!(e.expected, " from ");
+        assert_eq!(
+            e.to_string(),
+            "line 2, column 7: expected \" from \", found \"frm\""
+        );
+    }
+}
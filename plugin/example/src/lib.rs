@@ -0,0 +1,55 @@
+//! Day 6 as a plugin: the characters of the window are tracked in a bitmask instead of
+//! rebuilding a set for every position.
+//!
+//! Build it with `cargo build --release -p day6-bitmask` and copy the library from
+//! `target/release` into the `plugins` folder of the runner.
+
+use plugin::{export_solution, Solution};
+
+/// Index of the first byte after a run of `size` distinct lowercase letters.
+pub fn find_marker(buf: &[u8], size: usize) -> Option<usize> {
+    let mut counts = [0u8; 26];
+    let mut mask = 0u32;
+    for (i, &c) in buf.iter().enumerate() {
+        let c = c.checked_sub(b'a').filter(|c| *c < 26)? as usize;
+        counts[c] += 1;
+        mask |= 1 << c;
+        if i >= size {
+            let old = (buf[i - size] - b'a') as usize;
+            counts[old] -= 1;
+            if counts[old] == 0 {
+                mask &= !(1 << old);
+            }
+        }
+        if mask.count_ones() as usize == size {
+            return Some(i + 1);
+        }
+    }
+    None
+}
+
+pub struct Day6Bitmask;
+impl Solution for Day6Bitmask {
+    type Model = Vec<u8>;
+
+    fn parse(input: &str) -> Result<Self::Model, String> {
+        Ok(input.trim_end().as_bytes().to_vec())
+    }
+    fn part1(model: &Self::Model) -> Result<String, String> {
+        let marker = find_marker(model, 4).ok_or("No start-of-packet marker")?;
+        Ok(marker.to_string())
+    }
+    fn part2(model: &Self::Model) -> Result<String, String> {
+        let marker = find_marker(model, 14).ok_or("No start-of-message marker")?;
+        Ok(marker.to_string())
+    }
+}
+
+export_solution!(Day6Bitmask, 2022, 6, "bitmask window");
+
+#[test]
+fn examples() {
+    assert_eq!(find_marker(b"mjqjpqmgbljsphdztnvjfqwrcgsmlb", 4), Some(7));
+    assert_eq!(find_marker(b"mjqjpqmgbljsphdztnvjfqwrcgsmlb", 14), Some(19));
+    assert_eq!(find_marker(b"abc", 4), None);
+}
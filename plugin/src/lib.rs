@@ -0,0 +1,231 @@
+//! The `Solution` interface shared by the runner and the solver plugins, and the C ABI used to
+//! load a solution from a shared library.
+//!
+//! A plugin is a `cdylib` crate that implements `Solution` and exports it with
+//! [`export_solution!`]. The runner looks up the `aoc_plugin_v2` symbol, checks
+//! [`ABI_VERSION`] and talks to the solution through [`PluginV2`] only, so the plugin may be
+//! built by another compiler version than the runner.
+
+use std::ffi::c_void;
+use std::os::raw::c_char;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+/// A puzzle solver. The input is parsed once and both parts are computed from the model.
+pub trait Solution {
+    type Model;
+
+    fn parse(input: &str) -> Result<Self::Model, String>;
+    fn part1(model: &Self::Model) -> Result<String, String>;
+    fn part2(model: &Self::Model) -> Result<String, String>;
+}
+
+/// Bumped on any change to [`PluginV2`] or the types it uses.
+pub const ABI_VERSION: u32 = 2;
+
+/// Name of the symbol returning the [`PluginV2`] of a library.
+pub const ENTRY_POINT: &[u8] = b"aoc_plugin_v2";
+
+/// A string allocated by the plugin. Give it back with `free_text` once read.
+#[repr(C)]
+pub struct Text {
+    pub ptr: *mut u8,
+    pub len: usize,
+    pub cap: usize,
+}
+
+impl Text {
+    fn new(s: String) -> Text {
+        let mut s = std::mem::ManuallyDrop::new(s);
+        Text {
+            ptr: s.as_mut_ptr(),
+            len: s.len(),
+            cap: s.capacity(),
+        }
+    }
+
+    /// Copy of the text.
+    ///
+    /// # Safety
+    /// `self` must come from a plugin and not be freed yet.
+    pub unsafe fn to_string(&self) -> String {
+        let bytes = std::slice::from_raw_parts(self.ptr, self.len);
+        String::from_utf8_lossy(bytes).into_owned()
+    }
+}
+
+/// `Ok` or `Err` of a step, the text being the answer or the error message.
+#[repr(C)]
+pub struct Outcome {
+    pub ok: bool,
+    pub text: Text,
+}
+
+impl Outcome {
+    fn new(result: Result<String, String>) -> Outcome {
+        match result {
+            Ok(s) => Outcome {
+                ok: true,
+                text: Text::new(s),
+            },
+            Err(s) => Outcome {
+                ok: false,
+                text: Text::new(s),
+            },
+        }
+    }
+}
+
+/// Entry points of a plugin. Models are opaque to the runner, which must release them with
+/// `free_model`. Pointers given to the functions must come from the same plugin.
+#[repr(C)]
+pub struct PluginV2 {
+    pub abi_version: u32,
+    pub year: u32,
+    pub day: u32,
+    /// Nul terminated name of the implementation.
+    pub name: *const c_char,
+    /// Parse `len` bytes of UTF-8 input. On success `model` points to the parsed model.
+    pub parse:
+        unsafe extern "C" fn(input: *const u8, len: usize, model: *mut *mut c_void) -> Outcome,
+    pub part1: unsafe extern "C" fn(model: *const c_void) -> Outcome,
+    pub part2: unsafe extern "C" fn(model: *const c_void) -> Outcome,
+    pub free_model: unsafe extern "C" fn(model: *mut c_void),
+    pub free_text: unsafe extern "C" fn(text: Text),
+}
+
+// The name is a static string and the rest are function pointers.
+unsafe impl Sync for PluginV2 {}
+
+/// Run `f`, turning a panic into an error as it must not unwind into the runner.
+fn guard(f: impl FnOnce() -> Result<String, String>) -> Outcome {
+    Outcome::new(catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|_| Err("panicked".to_string())))
+}
+
+/// # Safety
+/// Called by the runner with pointers obtained from this plugin.
+#[doc(hidden)]
+pub unsafe extern "C" fn ffi_parse<S: Solution>(
+    input: *const u8,
+    len: usize,
+    model: *mut *mut c_void,
+) -> Outcome {
+    guard(|| {
+        // The runner passes a valid buffer and somewhere to store the model.
+        let input = std::slice::from_raw_parts(input, len);
+        let input = std::str::from_utf8(input).map_err(|e| e.to_string())?;
+        let parsed = S::parse(input)?;
+        *model = Box::into_raw(Box::new(parsed)) as *mut c_void;
+        Ok(String::new())
+    })
+}
+
+/// # Safety
+/// Called by the runner with pointers obtained from this plugin.
+#[doc(hidden)]
+pub unsafe extern "C" fn ffi_part1<S: Solution>(model: *const c_void) -> Outcome {
+    // `model` was returned by `ffi_parse::<S>` and is not freed yet.
+    guard(|| S::part1(&*(model as *const S::Model)))
+}
+
+/// # Safety
+/// Called by the runner with pointers obtained from this plugin.
+#[doc(hidden)]
+pub unsafe extern "C" fn ffi_part2<S: Solution>(model: *const c_void) -> Outcome {
+    // `model` was returned by `ffi_parse::<S>` and is not freed yet.
+    guard(|| S::part2(&*(model as *const S::Model)))
+}
+
+/// # Safety
+/// Called by the runner with pointers obtained from this plugin.
+#[doc(hidden)]
+pub unsafe extern "C" fn ffi_free_model<S: Solution>(model: *mut c_void) {
+    // `model` was returned by `ffi_parse::<S>` and is freed only once.
+    drop(Box::from_raw(model as *mut S::Model));
+}
+
+/// # Safety
+/// Called by the runner with pointers obtained from this plugin.
+#[doc(hidden)]
+pub unsafe extern "C" fn ffi_free_text(text: Text) {
+    // `text` was built by `Text::new` from a `String` of this library.
+    drop(String::from_raw_parts(text.ptr, text.len, text.cap));
+}
+
+/// Export a `Solution` from a `cdylib`, eg:
+/// `export_solution!(Day6Bits, 2022, 6, "bitmask window");`
+#[macro_export]
+macro_rules! export_solution {
+    ($solution:ty, $year:expr, $day:expr, $name:expr) => {
+        #[no_mangle]
+        pub extern "C" fn aoc_plugin_v2() -> *const $crate::PluginV2 {
+            static PLUGIN: $crate::PluginV2 = $crate::PluginV2 {
+                abi_version: $crate::ABI_VERSION,
+                year: $year,
+                day: $day,
+                name: concat!($name, "\0").as_ptr() as *const ::std::os::raw::c_char,
+                parse: $crate::ffi_parse::<$solution>,
+                part1: $crate::ffi_part1::<$solution>,
+                part2: $crate::ffi_part2::<$solution>,
+                free_model: $crate::ffi_free_model::<$solution>,
+                free_text: $crate::ffi_free_text,
+            };
+            &PLUGIN
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CStr;
+
+    struct Lines;
+    impl Solution for Lines {
+        type Model = Vec<String>;
+
+        fn parse(input: &str) -> Result<Self::Model, String> {
+            Ok(input.lines().map(String::from).collect())
+        }
+        fn part1(model: &Self::Model) -> Result<String, String> {
+            Ok(model.len().to_string())
+        }
+        fn part2(_model: &Self::Model) -> Result<String, String> {
+            panic!("not solved")
+        }
+    }
+
+    export_solution!(Lines, 2022, 42, "count lines");
+
+    fn read(plugin: &PluginV2, outcome: Outcome) -> Result<String, String> {
+        let text = unsafe { outcome.text.to_string() };
+        unsafe { (plugin.free_text)(outcome.text) };
+        if outcome.ok {
+            Ok(text)
+        } else {
+            Err(text)
+        }
+    }
+
+    #[test]
+    fn call_through_the_abi() {
+        let plugin = unsafe { &*aoc_plugin_v2() };
+        assert_eq!(plugin.abi_version, ABI_VERSION);
+        assert_eq!(plugin.year, 2022);
+        assert_eq!(plugin.day, 42);
+        let name = unsafe { CStr::from_ptr(plugin.name) };
+        assert_eq!(name.to_str(), Ok("count lines"));
+
+        let input = "a\nb\nc\n";
+        let mut model = std::ptr::null_mut();
+        unsafe {
+            let parsed = (plugin.parse)(input.as_ptr(), input.len(), &mut model);
+            assert_eq!(read(plugin, parsed), Ok(String::new()));
+            assert_eq!(read(plugin, (plugin.part1)(model)), Ok("3".to_string()));
+            assert_eq!(
+                read(plugin, (plugin.part2)(model)),
+                Err("panicked".to_string())
+            );
+            (plugin.free_model)(model);
+        }
+    }
+}